@@ -8,6 +8,7 @@
 //! - `{FONT_DIR}/{name}-Bold.ttf`
 //! - `{FONT_DIR}/{name}-Italic.ttf`
 //! - `{FONT_DIR}/{name}-BoldItalic.ttf`
+//!
 //! for `name` in {`DEFAULT_FONT_NAME`, `MONO_FONT_NAME`}.
 //!
 //! The generated document should be identical to the `examples/demo.pdf` document that is shipped
@@ -18,10 +19,10 @@ use std::env;
 use genpdf::Element as _;
 use genpdf::{elements, fonts, style};
 
-const FONT_DIR: &'static str = "/usr/share/fonts/truetype/liberation";
-const DEFAULT_FONT_NAME: &'static str = "LiberationSans";
-const MONO_FONT_NAME: &'static str = "LiberationMono";
-const LOREM_IPSUM: &'static str =
+const FONT_DIR: &str = "/usr/share/fonts/truetype/liberation";
+const DEFAULT_FONT_NAME: &str = "LiberationSans";
+const MONO_FONT_NAME: &str = "LiberationMono";
+const LOREM_IPSUM: &str =
     "Lorem ipsum dolor sit amet, consectetur adipiscing elit, sed do eiusmod tempor incididunt ut \
     labore et dolore magna aliqua. Ut enim ad minim veniam, quis nostrud exercitation ullamco \
     laboris nisi ut aliquip ex ea commodo consequat. Duis aute irure dolor in reprehenderit in \
@@ -184,6 +185,16 @@ fn main() {
             .framed()
             .styled(red),
     );
+    doc.push(
+        elements::Paragraph::new("This paragraph has a background color and rounded corners.")
+            .padded(genpdf::Margins::vh(1, 2))
+            .decorated(
+                style::BoxDecoration::new()
+                    .with_background_color(style::Color::Rgb(230, 240, 255))
+                    .with_left_border(style::LineStyle::from(blue).with_thickness(1))
+                    .with_corner_radius(2),
+            ),
+    );
     doc.push(
         elements::Paragraph::new("You can also use other fonts if you want to.").styled(monospace),
     );
@@ -196,6 +207,25 @@ fn main() {
             .string(" in one paragraph.")
             .styled(style::Style::new().with_font_size(16)),
     );
+    doc.push(
+        elements::Paragraph::default()
+            .string("Text can be ")
+            .styled_string("underlined", style::Effect::Underline)
+            .string(", ")
+            .styled_string("struck through", style::Effect::Strikethrough)
+            .string(" or ")
+            .styled_string("overlined", style::Effect::Overline)
+            .string("."),
+    );
+    doc.push(
+        elements::Paragraph::default()
+            .string("Superscripts and subscripts are also supported: E = mc")
+            .styled_string("2", style::Effect::Superscript)
+            .string(", H")
+            .styled_string("2", style::Effect::Subscript)
+            .string("O.")
+            .footnote("Footnotes are numbered automatically and placed at the bottom of the page."),
+    );
     doc.push(elements::Break::new(1.5));
 
     doc.push(elements::Paragraph::new("Here is an example table:"));
@@ -253,12 +283,74 @@ fn main() {
     doc.push(table);
     doc.push(elements::Break::new(1.5));
 
+    doc.push(elements::Paragraph::new(
+        "Shapes can be drawn with fill and stroke colors:",
+    ));
+    doc.push(elements::Break::new(0.5));
+    let fill = style::ShapeStyle::new().with_fill_color(style::Color::Rgb(200, 220, 255));
+    let mut shapes = elements::LinearLayout::horizontal();
+    shapes.push(elements::Shape::rectangle((30, 15)).with_style(fill.clone()));
+    shapes.push(elements::Shape::rounded_rectangle((30, 15), 3).with_style(fill.clone()));
+    shapes.push(
+        elements::Shape::ellipse((30, 15)).with_style(
+            fill.clone()
+                .with_fill_opacity(0.5)
+                .with_blend_mode(style::BlendMode::Multiply),
+        ),
+    );
+    shapes.push(
+        elements::Shape::polygon(vec![
+            genpdf::Position::new(15, 0),
+            genpdf::Position::new(30, 15),
+            genpdf::Position::new(0, 15),
+        ])
+        .with_style(fill),
+    );
+    doc.push(shapes);
+    doc.push(elements::Break::new(0.5));
+    doc.push(
+        elements::Paragraph::default()
+            .string("Text, lines and shapes can also be ")
+            .styled_string(
+                "semi-transparent",
+                style::Style::from(blue).with_opacity(0.4),
+            )
+            .string("."),
+    );
+    doc.push(elements::Break::new(0.5));
+    let mut transformed = elements::LinearLayout::horizontal();
+    transformed.push(
+        elements::Text::new("Rotated")
+            .transformed(genpdf::render::Transform::rotate(90.0))
+            .padded(genpdf::Margins::vh(0, 2)),
+    );
+    transformed.push(
+        elements::Paragraph::new("Elements can be rotated, scaled or skewed.")
+            .framed()
+            .transformed(genpdf::render::Transform::skew(-15.0, 0.0)),
+    );
+    doc.push(transformed);
+    doc.push(elements::Break::new(1.5));
+
     doc.push(elements::Paragraph::new(
         "Now let’s print a long table to demonstrate how page wrapping works:",
     ));
 
     let mut table = elements::TableLayout::new(vec![1, 5]);
-    table.set_cell_decorator(elements::FrameCellDecorator::new(true, true, false));
+    table.set_cell_decorator(
+        elements::ShadingCellDecorator::new()
+            .with_header_color(style::Color::Rgb(220, 220, 220))
+            .with_stripe_colors(None, style::Color::Rgb(245, 245, 245))
+            .with_decorator(
+                elements::FrameCellDecorator::new(true, true, false)
+                    .with_line_style(style::LineStyle::new().with_thickness(0.1))
+                    .with_outer_line_style(
+                        style::LineStyle::new()
+                            .with_thickness(0.6)
+                            .with_cap(style::LineCap::Square),
+                    ),
+            ),
+    );
     table
         .row()
         .element(
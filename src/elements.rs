@@ -8,18 +8,29 @@
 //!
 //! It includes the following elements:
 //! - Containers:
-//!   - [`LinearLayout`][]: arranges its elements sequentially
+//!   - [`LinearLayout`][]: arranges its elements sequentially (vertically or horizontally)
 //!   - [`TableLayout`][]: arranges its elements in columns and rows
+//!   - [`ColumnLayout`][]: flows its elements through multiple columns
 //!   - [`OrderedList`][] and [`UnorderedList`][]: arrange their elements sequentially with bullet
 //!     points
 //! - Text:
 //!   - [`Text`][]: a single line of text
 //!   - [`Paragraph`][]: a wrapped and aligned paragraph of text
+//!   - [`Heading`][]: a heading that adds an entry to the document outline
+//!   - [`TableOfContents`][]: a list of headings with their page numbers
 //! - Wrappers:
 //!   - [`FramedElement`][]: draws a frame around the wrapped element
+//!   - [`DecoratedElement`][]: draws a box with a background and borders around the wrapped
+//!     element
 //!   - [`PaddedElement`][]: adds a padding to the wrapped element
 //!   - [`StyledElement`][]: sets a default style for the wrapped element and its children
+//!   - [`Anchor`][]: adds a named destination for internal links at the wrapped element
+//!   - [`KeepTogether`][]: moves the wrapped element to the next page instead of splitting it
+//!   - [`KeepWithNext`][]: keeps the wrapped element on the same page as the next element
+//!   - [`Transformed`][]: rotates, scales or skews the wrapped element
 //! - Other:
+//!   - [`Image`][]: an image (*only available if the `images` feature is enabled*)
+//!   - [`Shape`][]: a rectangle, ellipse, polygon or path
 //!   - [`Break`][]: adds forced line breaks as a spacer
 //!   - [`PageBreak`][]: adds a forced page break
 //!
@@ -28,29 +39,62 @@
 //! [`Element`]: ../trait.Element.html
 //! [`LinearLayout`]: struct.LinearLayout.html
 //! [`TableLayout`]: struct.TableLayout.html
+//! [`ColumnLayout`]: struct.ColumnLayout.html
 //! [`OrderedList`]: struct.OrderedList.html
 //! [`UnorderedList`]: struct.UnorderedList.html
 //! [`Text`]: struct.Text.html
+//! [`Image`]: struct.Image.html
+//! [`Shape`]: struct.Shape.html
 //! [`Break`]: struct.Break.html
 //! [`PageBreak`]: struct.PageBreak.html
 //! [`Paragraph`]: struct.Paragraph.html
+//! [`Heading`]: struct.Heading.html
+//! [`TableOfContents`]: struct.TableOfContents.html
 //! [`FramedElement`]: struct.FramedElement.html
+//! [`DecoratedElement`]: struct.DecoratedElement.html
 //! [`PaddedElement`]: struct.PaddedElement.html
 //! [`StyledElement`]: struct.StyledElement.html
+//! [`Anchor`]: struct.Anchor.html
+//! [`KeepTogether`]: struct.KeepTogether.html
+//! [`KeepWithNext`]: struct.KeepWithNext.html
+//! [`Transformed`]: struct.Transformed.html
 
+#[cfg(feature = "images")]
+mod images;
+
+use std::cell;
 use std::collections;
 use std::iter;
 use std::mem;
+use std::rc;
 
 use crate::error::{Error, ErrorKind};
 use crate::render;
-use crate::style::{Style, StyledString};
+use crate::style::{self, Style, StyledString};
 use crate::wrap;
-use crate::{Context, Element, Margins, Mm, Position, RenderResult, Size};
+use crate::{ContentWidth, Context, Element, Margins, Mm, Position, RenderResult, Size};
+
+#[cfg(feature = "images")]
+pub use images::Image;
 
 /// Arranges a list of elements sequentially.
 ///
-/// Currently, elements can only be arranged vertically.
+/// The elements can be arranged vertically (see [`vertical`][]) or horizontally (see
+/// [`horizontal`][]).
+///
+/// In a vertical layout, the elements are rendered one after another, each using the full width of
+/// the provided area.  If an element does not fit on the current page, it is continued on the next
+/// page.  Elements that should be kept with the next element (see [`Element::keep_with_next`][])
+/// are moved to the next page if they don’t fit on the current page together with the first line
+/// of the next element.
+///
+/// In a horizontal layout, the elements are placed side by side.  The width of each element is
+/// determined by its [`ColumnWidth`][]:  Elements with a fixed width are rendered with exactly
+/// that width, elements with an automatic width are sized to fit their content (see
+/// [`Element::content_width`][]) and the remaining space is distributed among the weighted
+/// elements.  The elements are aligned vertically according to the [`VerticalAlignment`][] of the
+/// layout.  If one of the elements does not fit on the current page, it is continued on the next
+/// page while the other elements are only rendered once.
 ///
 /// # Examples
 ///
@@ -70,27 +114,84 @@ use crate::{Context, Element, Margins, Mm, Position, RenderResult, Size};
 ///     .element(elements::Paragraph::new("Test2"));
 /// ```
 ///
+/// Horizontal layout:
+/// ```
+/// use genpdf::{elements, Mm};
+/// let layout = elements::LinearLayout::horizontal()
+///     .element_with_width(elements::Paragraph::new("Fixed"), Mm::from(30))
+///     .element_with_width(elements::Paragraph::new("Sized to fit"), elements::ColumnWidth::Auto)
+///     .element(elements::Paragraph::new("Remaining space"))
+///     .with_vertical_alignment(elements::VerticalAlignment::Center);
+/// ```
+///
+/// [`vertical`]: #method.vertical
+/// [`horizontal`]: #method.horizontal
+/// [`ColumnWidth`]: enum.ColumnWidth.html
+/// [`VerticalAlignment`]: enum.VerticalAlignment.html
+/// [`Element::content_width`]: ../trait.Element.html#method.content_width
+/// [`Element::keep_with_next`]: ../trait.Element.html#method.keep_with_next
 pub struct LinearLayout {
+    orientation: Orientation,
     elements: Vec<Box<dyn Element>>,
+    widths: Vec<ColumnWidth>,
+    vertical_alignment: VerticalAlignment,
     render_idx: usize,
+    // only used for vertical layouts:  the index of the element that has already been moved to the
+    // next page or that has been rendered partially and that must not be moved again
+    split_idx: Option<usize>,
+    // only used for horizontal layouts
+    content_widths: Option<Vec<Option<ContentWidth>>>,
+    is_finished: Vec<bool>,
 }
 
 impl LinearLayout {
-    fn new() -> LinearLayout {
+    fn new(orientation: Orientation) -> LinearLayout {
         LinearLayout {
+            orientation,
             elements: Vec::new(),
+            widths: Vec::new(),
+            vertical_alignment: VerticalAlignment::default(),
             render_idx: 0,
+            split_idx: None,
+            content_widths: None,
+            is_finished: Vec::new(),
         }
     }
 
     /// Creates a new linear layout that arranges its elements vertically.
     pub fn vertical() -> LinearLayout {
-        LinearLayout::new()
+        LinearLayout::new(Orientation::Vertical)
+    }
+
+    /// Creates a new linear layout that arranges its elements horizontally.
+    pub fn horizontal() -> LinearLayout {
+        LinearLayout::new(Orientation::Horizontal)
+    }
+
+    /// Sets the vertical alignment of the elements of this layout.
+    ///
+    /// The vertical alignment is only used for horizontal layouts.  It requires that the elements
+    /// support [`Element::content_height`][].  Elements that don’t support it are always aligned
+    /// at the top.
+    ///
+    /// [`Element::content_height`]: ../trait.Element.html#method.content_height
+    pub fn set_vertical_alignment(&mut self, alignment: VerticalAlignment) {
+        self.vertical_alignment = alignment;
+    }
+
+    /// Sets the vertical alignment of the elements of this layout and returns the layout.
+    pub fn with_vertical_alignment(mut self, alignment: VerticalAlignment) -> Self {
+        self.set_vertical_alignment(alignment);
+        self
     }
 
     /// Adds the given element to this layout.
+    ///
+    /// In a horizontal layout, the element has the weight 1, see [`ColumnWidth::Weighted`][].
+    ///
+    /// [`ColumnWidth::Weighted`]: enum.ColumnWidth.html#variant.Weighted
     pub fn push<E: Element + 'static>(&mut self, element: E) {
-        self.elements.push(Box::new(element));
+        self.push_with_width(element, ColumnWidth::default());
     }
 
     /// Adds the given element to this layout and it returns the layout.
@@ -99,6 +200,30 @@ impl LinearLayout {
         self
     }
 
+    /// Adds the given element with the given width to this layout.
+    ///
+    /// The width is only used for horizontal layouts.  In a vertical layout, all elements use the
+    /// full width of the layout.
+    pub fn push_with_width<E: Element + 'static>(
+        &mut self,
+        element: E,
+        width: impl Into<ColumnWidth>,
+    ) {
+        self.elements.push(Box::new(element));
+        self.widths.push(width.into());
+        self.is_finished.push(false);
+    }
+
+    /// Adds the given element with the given width to this layout and returns the layout.
+    pub fn element_with_width<E: Element + 'static>(
+        mut self,
+        element: E,
+        width: impl Into<ColumnWidth>,
+    ) -> Self {
+        self.push_with_width(element, width);
+        self
+    }
+
     fn render_vertical(
         &mut self,
         context: &Context,
@@ -107,11 +232,20 @@ impl LinearLayout {
     ) -> Result<RenderResult, Error> {
         let mut result = RenderResult::default();
         while area.size().height > Mm(0.0) && self.render_idx < self.elements.len() {
+            if self.split_idx != Some(self.render_idx) {
+                let height = self.get_kept_height(context, style, area.size().width);
+                if height.map(|h| h > area.size().height).unwrap_or_default() {
+                    self.split_idx = Some(self.render_idx);
+                    result.has_more = true;
+                    return Ok(result);
+                }
+            }
             let element_result =
                 self.elements[self.render_idx].render(context, area.clone(), style)?;
             area.add_offset(Position::new(0, element_result.size.height));
             result.size = result.size.stack_vertical(element_result.size);
             if element_result.has_more {
+                self.split_idx = Some(self.render_idx);
                 result.has_more = true;
                 return Ok(result);
             }
@@ -120,6 +254,134 @@ impl LinearLayout {
         result.has_more = self.render_idx < self.elements.len();
         Ok(result)
     }
+
+    /// Returns the height that is required to render the current element if it should be kept
+    /// with the next element, or `None` if it should not be kept with the next element or if the
+    /// height is unknown.
+    ///
+    /// The required height is the height of the current element and of all following elements
+    /// that should be kept with the next element, plus the height of the first line of the next
+    /// element.
+    fn get_kept_height(&self, context: &Context, style: Style, width: Mm) -> Option<Mm> {
+        let elements = &self.elements[self.render_idx..];
+        let n = elements
+            .iter()
+            .take_while(|element| element.keeps_with_next())
+            .count();
+        if n == 0 {
+            return None;
+        }
+        let mut height = Mm(0.0);
+        for element in &elements[..n] {
+            height += element.content_height(context, style, width)?;
+        }
+        // We don’t know the height of the first line of the next element, so we use the line
+        // height of the current style or the height of the element, whatever is smaller.
+        if let Some(next) = elements.get(n) {
+            let line_height = style.line_height(&context.font_cache);
+            height += next
+                .content_height(context, style, width)
+                .map(|h| h.min(line_height))
+                .unwrap_or(line_height);
+        }
+        Some(height)
+    }
+
+    fn render_horizontal(
+        &mut self,
+        context: &Context,
+        area: render::Area<'_>,
+        style: Style,
+    ) -> Result<RenderResult, Error> {
+        let mut result = RenderResult::default();
+
+        // We have to measure the elements before rendering them for the first time as their
+        // content width changes during the rendering process.
+        if self.content_widths.is_none() {
+            self.content_widths = Some(self.measure_content_widths(context, style));
+        }
+        let widths = self.get_widths(context, style, area.size().width);
+
+        let heights: Vec<_> = if self.vertical_alignment == VerticalAlignment::Top {
+            vec![None; self.elements.len()]
+        } else {
+            self.elements
+                .iter()
+                .zip(&widths)
+                .zip(&self.is_finished)
+                .map(|((element, width), is_finished)| {
+                    if *is_finished {
+                        None
+                    } else {
+                        element.content_height(context, style, *width)
+                    }
+                })
+                .map(|height| height.filter(|height| *height <= area.size().height))
+                .collect()
+        };
+
+        let mut areas = Vec::with_capacity(widths.len());
+        let mut offset = Mm(0.0);
+        for width in &widths {
+            let mut element_area = area.clone();
+            element_area.add_offset(Position::new(offset, 0));
+            element_area.set_width(*width);
+            areas.push(element_area);
+            offset += *width;
+        }
+
+        // First, we render the elements that we could not measure and that are always aligned at
+        // the top.  Then we know the height of the row and can align the remaining elements.
+        let mut row_height = heights
+            .iter()
+            .filter_map(|height| *height)
+            .fold(Mm(0.0), Mm::max);
+        let mut is_empty = true;
+        for measured in &[false, true] {
+            for i in 0..self.elements.len() {
+                if self.is_finished[i] || heights[i].is_some() != *measured {
+                    continue;
+                }
+                let mut element_area = areas[i].clone();
+                let offset = heights[i]
+                    .map(|height| self.vertical_alignment.get_offset(height, row_height))
+                    .unwrap_or_default();
+                element_area.add_offset(Position::new(0, offset));
+                let element_result = self.elements[i].render(context, element_area, style)?;
+                is_empty &= element_result.size == Size::default();
+                row_height = row_height.max(offset + element_result.size.height);
+                self.is_finished[i] = !element_result.has_more;
+                result.has_more |= element_result.has_more;
+            }
+        }
+
+        // If none of the elements could be rendered, we have to return an empty size, see the
+        // documentation of Element::render.
+        if !(result.has_more && is_empty) {
+            result.size = Size::new(offset, row_height);
+        }
+        Ok(result)
+    }
+
+    fn measure_content_widths(&self, context: &Context, style: Style) -> Vec<Option<ContentWidth>> {
+        self.elements
+            .iter()
+            .zip(&self.widths)
+            .map(|(element, width)| match width {
+                ColumnWidth::Auto => element.content_width(context, style),
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn get_widths(&self, context: &Context, style: Style, width: Mm) -> Vec<Mm> {
+        if let Some(content_widths) = &self.content_widths {
+            distribute_widths(&self.widths, content_widths, width)
+        } else {
+            let content_widths = self.measure_content_widths(context, style);
+            distribute_widths(&self.widths, &content_widths, width)
+        }
+    }
 }
 
 impl Element for LinearLayout {
@@ -129,8 +391,301 @@ impl Element for LinearLayout {
         area: render::Area<'_>,
         style: Style,
     ) -> Result<RenderResult, Error> {
-        // TODO: add horizontal layout
-        self.render_vertical(context, area, style)
+        match self.orientation {
+            Orientation::Vertical => self.render_vertical(context, area, style),
+            Orientation::Horizontal => self.render_horizontal(context, area, style),
+        }
+    }
+
+    fn content_width(&self, context: &Context, style: Style) -> Option<ContentWidth> {
+        let mut result = ContentWidth::default();
+        match self.orientation {
+            Orientation::Vertical => {
+                for element in &self.elements[self.render_idx..] {
+                    result = result.stack_vertical(element.content_width(context, style)?);
+                }
+            }
+            Orientation::Horizontal => {
+                for (element, width) in self.elements.iter().zip(&self.widths) {
+                    let content_width = match width {
+                        ColumnWidth::Fixed(width) => ContentWidth::fixed(*width),
+                        _ => element.content_width(context, style)?,
+                    };
+                    result = result.stack_horizontal(content_width);
+                }
+            }
+        }
+        Some(result)
+    }
+
+    fn content_height(&self, context: &Context, style: Style, width: Mm) -> Option<Mm> {
+        let mut result = Mm(0.0);
+        match self.orientation {
+            Orientation::Vertical => {
+                for element in &self.elements[self.render_idx..] {
+                    result += element.content_height(context, style, width)?;
+                }
+            }
+            Orientation::Horizontal => {
+                let widths = self.get_widths(context, style, width);
+                for (i, element) in self.elements.iter().enumerate() {
+                    if !self.is_finished[i] {
+                        result = result.max(element.content_height(context, style, widths[i])?);
+                    }
+                }
+            }
+        }
+        Some(result)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Orientation {
+    Vertical,
+    Horizontal,
+}
+
+/// The width of a column, for example of an element in a horizontal [`LinearLayout`][].
+///
+/// The default column width is a weight of 1.
+///
+/// [`LinearLayout`]: struct.LinearLayout.html
+#[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
+pub enum ColumnWidth {
+    /// A fixed width.
+    Fixed(Mm),
+    /// A relative width.
+    ///
+    /// The space that is not used by columns with a fixed or an automatic width is distributed
+    /// among the weighted columns according to their weights.
+    Weighted(usize),
+    /// A width that is determined by the content of the column.
+    ///
+    /// The column uses the maximum content width (see [`Element::content_width`][]) if there is
+    /// enough space.  Otherwise, it is shrinked, but not narrower than the minimum content width.
+    /// If the content width cannot be measured, the column is treated like a column with the
+    /// weight 1.
+    ///
+    /// [`Element::content_width`]: ../trait.Element.html#method.content_width
+    Auto,
+}
+
+impl Default for ColumnWidth {
+    fn default() -> ColumnWidth {
+        ColumnWidth::Weighted(1)
+    }
+}
+
+impl From<Mm> for ColumnWidth {
+    fn from(width: Mm) -> ColumnWidth {
+        ColumnWidth::Fixed(width)
+    }
+}
+
+impl From<usize> for ColumnWidth {
+    fn from(weight: usize) -> ColumnWidth {
+        ColumnWidth::Weighted(weight)
+    }
+}
+
+/// Distributes the given width among columns with the given widths and content widths.
+///
+/// The content widths are only used for columns with an automatic width.
+fn distribute_widths(
+    widths: &[ColumnWidth],
+    content_widths: &[Option<ContentWidth>],
+    width: Mm,
+) -> Vec<Mm> {
+    let mut result = vec![Mm(0.0); widths.len()];
+    let mut remaining = width;
+    let mut weights = vec![0; widths.len()];
+    let mut auto = Vec::new();
+    for (i, (width, content_width)) in widths.iter().zip(content_widths).enumerate() {
+        match (width, content_width) {
+            (ColumnWidth::Fixed(width), _) => {
+                result[i] = *width;
+                remaining -= *width;
+            }
+            (ColumnWidth::Weighted(weight), _) => weights[i] = *weight,
+            (ColumnWidth::Auto, Some(content_width)) => auto.push((i, *content_width)),
+            (ColumnWidth::Auto, None) => weights[i] = 1,
+        }
+    }
+
+    // Automatic columns use their maximum width if possible.  Otherwise, the available space is
+    // distributed proportionally to the difference between their minimum and maximum width.
+    let min: Mm = auto.iter().map(|(_, w)| w.min).sum();
+    let max: Mm = auto.iter().map(|(_, w)| w.max).sum();
+    let factor = if max <= remaining {
+        1.0
+    } else if min >= remaining || max <= min {
+        0.0
+    } else {
+        (remaining - min).0 / (max - min).0
+    };
+    for (i, content_width) in auto {
+        result[i] = content_width.min + (content_width.max - content_width.min) * factor;
+        remaining -= result[i];
+    }
+
+    let total_weight: usize = weights.iter().sum();
+    if total_weight > 0 && remaining > Mm(0.0) {
+        for (i, weight) in weights.into_iter().enumerate() {
+            if weight > 0 {
+                result[i] = remaining * weight as f64 / total_weight as f64;
+            }
+        }
+    }
+
+    result
+}
+
+/// The vertical alignment of elements in a horizontal [`LinearLayout`][].
+///
+/// The default alignment is top-aligned.
+///
+/// [`LinearLayout`]: struct.LinearLayout.html
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum VerticalAlignment {
+    /// Top-aligned.
+    Top,
+    /// Centered.
+    Center,
+    /// Bottom-aligned.
+    Bottom,
+}
+
+impl VerticalAlignment {
+    fn get_offset(&self, height: Mm, max_height: Mm) -> Mm {
+        match self {
+            VerticalAlignment::Top => Mm::default(),
+            VerticalAlignment::Center => (max_height - height) / 2.0,
+            VerticalAlignment::Bottom => max_height - height,
+        }
+    }
+}
+
+// #[default] on enum variants requires Rust 1.62, so we can't derive Default.
+#[allow(clippy::derivable_impls)]
+impl Default for VerticalAlignment {
+    fn default() -> VerticalAlignment {
+        VerticalAlignment::Top
+    }
+}
+
+/// Arranges a list of elements in multiple columns that are filled one after another.
+///
+/// The elements are rendered like in a vertical [`LinearLayout`][], but instead of continuing an
+/// element on the next page if it does not fit into the current area, it is continued in the next
+/// column.  Only if the last column on the page is full, the layout is continued on the next page.
+/// A [`PageBreak`][] in a column layout starts a new column.
+///
+/// The columns have the same width and are separated by a gutter that is 5 mm wide by default.
+///
+/// # Examples
+///
+/// With setters:
+/// ```
+/// use genpdf::{elements, Mm};
+/// let mut layout = elements::ColumnLayout::new(2);
+/// layout.set_gutter(Mm::from(10));
+/// layout.push(elements::Paragraph::new("Test1"));
+/// layout.push(elements::Paragraph::new("Test2"));
+/// ```
+///
+/// Chained:
+/// ```
+/// use genpdf::{elements, Mm};
+/// let layout = elements::ColumnLayout::new(2)
+///     .with_gutter(Mm::from(10))
+///     .element(elements::Paragraph::new("Test1"))
+///     .element(elements::Paragraph::new("Test2"));
+/// ```
+///
+/// [`LinearLayout`]: struct.LinearLayout.html
+/// [`PageBreak`]: struct.PageBreak.html
+pub struct ColumnLayout {
+    layout: LinearLayout,
+    columns: usize,
+    gutter: Mm,
+}
+
+impl ColumnLayout {
+    /// Creates a new column layout with the given number of columns.
+    pub fn new(columns: usize) -> ColumnLayout {
+        ColumnLayout {
+            layout: LinearLayout::vertical(),
+            columns,
+            gutter: Mm::from(5),
+        }
+    }
+
+    /// Sets the width of the gutter between the columns.
+    pub fn set_gutter(&mut self, gutter: impl Into<Mm>) {
+        self.gutter = gutter.into();
+    }
+
+    /// Sets the width of the gutter between the columns and returns the layout.
+    pub fn with_gutter(mut self, gutter: impl Into<Mm>) -> Self {
+        self.set_gutter(gutter);
+        self
+    }
+
+    /// Adds the given element to this layout.
+    pub fn push<E: Element + 'static>(&mut self, element: E) {
+        self.layout.push(element);
+    }
+
+    /// Adds the given element to this layout and returns the layout.
+    pub fn element<E: Element + 'static>(mut self, element: E) -> Self {
+        self.push(element);
+        self
+    }
+
+    fn get_column_width(&self, width: Mm) -> Mm {
+        let gutters = self.gutter * self.columns.saturating_sub(1) as f64;
+        (width - gutters) / self.columns as f64
+    }
+}
+
+impl Element for ColumnLayout {
+    fn render(
+        &mut self,
+        context: &Context,
+        area: render::Area<'_>,
+        style: Style,
+    ) -> Result<RenderResult, Error> {
+        let mut result = RenderResult::default();
+        if self.columns == 0 {
+            return Ok(result);
+        }
+        let width = self.get_column_width(area.size().width);
+        let mut is_empty = true;
+        for i in 0..self.columns {
+            let mut column_area = area.clone();
+            column_area.add_offset(Position::new((width + self.gutter) * i as f64, 0));
+            column_area.set_width(width);
+            let column_result = self.layout.render(context, column_area, style)?;
+            is_empty &= column_result.size == Size::default();
+            result.size.height = result.size.height.max(column_result.size.height);
+            result.has_more = column_result.has_more;
+            if !result.has_more {
+                break;
+            }
+        }
+        // If none of the columns could be rendered, we have to return an empty size, see the
+        // documentation of Element::render.
+        if !(result.has_more && is_empty) {
+            result.size.width = area.size().width;
+        }
+        Ok(result)
+    }
+
+    fn content_width(&self, context: &Context, style: Style) -> Option<ContentWidth> {
+        let content_width = self.layout.content_width(context, style)?;
+        let n = self.columns as f64;
+        let gutters = self.gutter * self.columns.saturating_sub(1) as f64;
+        Some(ContentWidth::new(content_width.min * n, content_width.max * n) + gutters)
     }
 }
 
@@ -162,12 +717,10 @@ impl Element for Text {
     ) -> Result<RenderResult, Error> {
         let mut result = RenderResult::default();
         style.merge(self.text.style);
-        if area.print_str(
-            &context.font_cache,
-            Position::default(),
-            style,
-            &self.text.s,
-        )? {
+        if let Ok(mut section) = area.text_section(&context.font_cache, Position::default(), style)
+        {
+            section.set_link(self.text.link.as_ref());
+            section.print_str(&self.text.s, style)?;
             result.size = Size::new(
                 style.str_width(&context.font_cache, &self.text.s),
                 style.line_height(&context.font_cache),
@@ -177,6 +730,18 @@ impl Element for Text {
         }
         Ok(result)
     }
+
+    fn content_width(&self, context: &Context, mut style: Style) -> Option<ContentWidth> {
+        style.merge(self.text.style);
+        Some(ContentWidth::fixed(
+            style.str_width(&context.font_cache, &self.text.s),
+        ))
+    }
+
+    fn content_height(&self, context: &Context, mut style: Style, _width: Mm) -> Option<Mm> {
+        style.merge(self.text.style);
+        Some(style.line_height(&context.font_cache))
+    }
 }
 
 /// The alignment of a [`Paragraph`][].
@@ -192,8 +757,18 @@ pub enum Alignment {
     Right,
     /// Centered.
     Center,
+    /// Justified.
+    ///
+    /// The space between the words is stretched so that the lines are left- and right-flushed.
+    /// The last line of a paragraph is left-flushed.  For elements that don’t consist of
+    /// multiple lines of text, this alignment is the same as [`Left`][].
+    ///
+    /// [`Left`]: #variant.Left
+    Justified,
 }
 
+// #[default] on enum variants requires Rust 1.62, so we can't derive Default.
+#[allow(clippy::derivable_impls)]
 impl Default for Alignment {
     fn default() -> Alignment {
         Alignment::Left
@@ -208,7 +783,15 @@ impl Default for Alignment {
 ///
 /// Use the [`push`][], [`string`][], [`push_styled`][] and [`string_styled`][] methods to add
 /// strings to this paragraph.  Besides the styling of the text (see [`Style`][]), you can also set
-/// an [`Alignment`][] for the paragraph.
+/// an [`Alignment`][] for the paragraph.  Strings with a [`Link`][] target are rendered as
+/// clickable links, even if they are wrapped across multiple lines or pages.  Strings with a
+/// [`Footnote`][] are rendered as footnote markers, and the footnote text is placed at the bottom
+/// of the page, see [`push_footnote`][].
+///
+/// If the paragraph does not fit on the current page, it is continued on the next page.  To avoid
+/// single lines at the bottom or at the top of a page, at least two lines are kept together at the
+/// start and at the end of the paragraph.  You can change these limits with the [`set_orphans`][]
+/// and [`set_widows`][] methods.
 ///
 /// Note that the line height and spacing is currently calculated based on the style of the entire
 /// paragraph.  If the font family or font size is changed in the [`Style`][] settings for a
@@ -222,7 +805,10 @@ impl Default for Alignment {
 /// let mut p = elements::Paragraph::default();
 /// p.push("This is an ");
 /// p.push_styled("important", style::Color::Rgb(255, 0, 0));
-/// p.push(" message!");
+/// p.push(" message!  See the ");
+/// p.push(style::StyledString::from("documentation").with_link(style::Link::Uri(
+///     "https://docs.rs/genpdf".to_owned(),
+/// )));
 /// p.set_alignment(elements::Alignment::Center);
 /// ```
 ///
@@ -237,17 +823,26 @@ impl Default for Alignment {
 /// ```
 ///
 /// [`Style`]: ../style/struct.Style.html
+/// [`Link`]: ../style/enum.Link.html
+/// [`Footnote`]: ../style/struct.Footnote.html
 /// [`Alignment`]: enum.Alignment.html
 /// [`push`]: #method.push
+/// [`push_footnote`]: #method.push_footnote
 /// [`push_styled`]: #method.push_styled
 /// [`string`]: #method.string
 /// [`string_styled`]: #method.string_styled
-#[derive(Clone, Debug, Default)]
+/// [`set_orphans`]: #method.set_orphans
+/// [`set_widows`]: #method.set_widows
+#[derive(Clone, Debug)]
 pub struct Paragraph {
     text: Vec<StyledString>,
     words: collections::VecDeque<StyledString>,
     style_applied: bool,
     alignment: Alignment,
+    orphans: usize,
+    widows: usize,
+    is_started: bool,
+    is_moved: bool,
 }
 
 impl Paragraph {
@@ -270,6 +865,38 @@ impl Paragraph {
         self
     }
 
+    /// Sets the minimum number of lines of this paragraph that are rendered at the bottom of a
+    /// page before a page break.
+    ///
+    /// If fewer lines fit on the current page, the paragraph is moved to the next page.  The
+    /// default value is 2.
+    pub fn set_orphans(&mut self, orphans: usize) {
+        self.orphans = orphans;
+    }
+
+    /// Sets the minimum number of lines of this paragraph that are rendered at the bottom of a
+    /// page before a page break and returns the paragraph.
+    pub fn with_orphans(mut self, orphans: usize) -> Self {
+        self.set_orphans(orphans);
+        self
+    }
+
+    /// Sets the minimum number of lines of this paragraph that are rendered at the top of a page
+    /// after a page break.
+    ///
+    /// If fewer lines would be left for the next page, more lines are moved to the next page.  The
+    /// default value is 2.
+    pub fn set_widows(&mut self, widows: usize) {
+        self.widows = widows;
+    }
+
+    /// Sets the minimum number of lines of this paragraph that are rendered at the top of a page
+    /// after a page break and returns the paragraph.
+    pub fn with_widows(mut self, widows: usize) -> Self {
+        self.set_widows(widows);
+        self
+    }
+
     /// Adds a string to the end of this paragraph.
     pub fn push(&mut self, s: impl Into<StyledString>) {
         self.text.push(s.into());
@@ -292,14 +919,73 @@ impl Paragraph {
         self
     }
 
+    /// Adds a reference to the given footnote to the end of this paragraph.
+    ///
+    /// The footnote is numbered automatically and the number is printed as a superscript marker.
+    /// The footnote text is placed at the bottom of the page that contains the marker, above the
+    /// footer, and continued on the next page if it does not fit.  To use a custom marker, attach
+    /// the footnote to a styled string instead, see [`StyledString::with_footnote`][].
+    ///
+    /// Footnotes are only supported for elements that are rendered in the document body.
+    ///
+    /// [`StyledString::with_footnote`]: ../style/struct.StyledString.html#method.with_footnote
+    pub fn push_footnote(&mut self, footnote: impl Into<style::Footnote>) {
+        self.push(
+            StyledString::new(String::new(), style::Effect::Superscript).with_footnote(footnote),
+        );
+    }
+
+    /// Adds a reference to the given footnote to the end of this paragraph and returns the
+    /// paragraph.
+    pub fn footnote(mut self, footnote: impl Into<style::Footnote>) -> Self {
+        self.push_footnote(footnote);
+        self
+    }
+
+    /// Returns the number of lines that should be rendered on the current page if the paragraph
+    /// has the given number of lines and the given number of lines fits on the current page.
+    fn get_line_count(&self, lines: usize, fitting: usize) -> usize {
+        if fitting >= lines {
+            return lines;
+        }
+        let mut count = fitting.min(lines.saturating_sub(self.widows));
+        if !self.is_started && count < self.orphans {
+            count = 0;
+        }
+        // The paragraph is moved to the next page at most once.  If it still does not fit, we
+        // ignore the orphans and widows settings.
+        if count == 0 && self.is_moved {
+            fitting
+        } else {
+            count
+        }
+    }
+
     fn get_offset(&self, width: Mm, max_width: Mm) -> Mm {
         match self.alignment {
-            Alignment::Left => Mm::default(),
+            Alignment::Left | Alignment::Justified => Mm::default(),
             Alignment::Center => (max_width - width) / 2.0,
             Alignment::Right => max_width - width,
         }
     }
 
+    /// Returns the words of this paragraph that have not been rendered yet, with the given style
+    /// applied if necessary.
+    fn get_remaining_words(&self, style: Style) -> Vec<StyledString> {
+        if self.words.is_empty() {
+            let text = self.text.iter().map(|s| {
+                let mut s = s.clone();
+                if !self.style_applied {
+                    s.style = style.and(s.style);
+                }
+                s
+            });
+            wrap::Words::new(text).collect()
+        } else {
+            self.words.iter().cloned().collect()
+        }
+    }
+
     fn apply_style(&mut self, style: Style) {
         if !self.style_applied {
             for s in &mut self.text {
@@ -310,6 +996,21 @@ impl Paragraph {
     }
 }
 
+impl Default for Paragraph {
+    fn default() -> Paragraph {
+        Paragraph {
+            text: Vec::new(),
+            words: collections::VecDeque::new(),
+            style_applied: false,
+            alignment: Alignment::default(),
+            orphans: 2,
+            widows: 2,
+            is_started: false,
+            is_moved: false,
+        }
+    }
+}
+
 impl Element for Paragraph {
     fn render(
         &mut self,
@@ -325,18 +1026,69 @@ impl Element for Paragraph {
             if self.text.is_empty() {
                 return Ok(result);
             }
+            for s in &mut self.text {
+                if s.footnote.is_some() && s.s.is_empty() {
+                    s.s = area.next_footnote_number().to_string();
+                }
+            }
             self.words = wrap::Words::new(mem::take(&mut self.text)).collect();
         }
 
         let height = style.line_height(&context.font_cache);
         let words = self.words.iter().map(Into::into);
+        let lines: Vec<_> = wrap::Wrapper::new(words, context, area.size().width).collect();
+        let footnotes = get_footnotes(context, &area, style, &lines);
+
+        // Use the same condition as Area::text_section to calculate the number of lines that fit
+        // into the area.  Lines with footnote markers also need space for the footnote text, but
+        // the last footnote of a line may be continued on the next page.
+        let glyph_height = style
+            .font(&context.font_cache)
+            .glyph_height(style.font_size());
+        let max_height = area.size().height + Mm(1e-6);
+        let mut fitting = 0;
+        let mut reserved = Mm(0.0);
+        for (i, line_footnotes) in footnotes.iter().enumerate() {
+            let mut required = reserved;
+            for (j, (_, height, min_height)) in line_footnotes.iter().enumerate() {
+                required += if j + 1 == line_footnotes.len() {
+                    *min_height
+                } else {
+                    *height
+                };
+            }
+            if height * i as f64 + glyph_height + required > max_height {
+                break;
+            }
+            fitting += 1;
+            reserved += line_footnotes.iter().map(|(_, height, _)| *height).sum();
+        }
+        let line_count = self.get_line_count(lines.len(), fitting);
+        if line_count < lines.len() {
+            result.has_more = true;
+        }
+
         let mut rendered_len = 0;
-        for (line, delta) in wrap::Wrapper::new(words, context, area.size().width) {
-            let width = line.iter().map(|s| s.width(&context.font_cache)).sum();
+        let mut footnotes = footnotes.into_iter();
+        let is_last_line = |i: usize| i + 1 == lines.len();
+        for (i, (line, delta)) in lines.iter().enumerate().take(line_count) {
+            for (element, height, _) in footnotes.next().unwrap_or_default() {
+                // If the footnote does not fit on this page, it is continued on the next page.
+                let max_height = (area.size().height - glyph_height).max(Mm(0.0));
+                area.add_footnote(element, height.min(max_height));
+            }
+            let mut width = line.iter().map(|s| s.width(&context.font_cache)).sum();
             let position = Position::new(self.get_offset(width, area.size().width), 0);
             // TODO: calculate the maximum line height
             if let Ok(mut section) = area.text_section(&context.font_cache, position, style) {
+                // The last line of a justified paragraph is left-flushed.
+                if self.alignment == Alignment::Justified && !is_last_line(i) {
+                    let spacing = get_word_spacing(context, line, area.size().width);
+                    section.set_word_spacing(spacing);
+                    width = width.max(area.size().width);
+                }
                 for s in line {
+                    section.set_link(s.link.as_deref());
                     section.print_str(&s.s, s.style)?;
                     rendered_len += s.s.len();
                 }
@@ -349,6 +1101,13 @@ impl Element for Paragraph {
             area.add_offset(Position::new(0, height));
         }
 
+        if rendered_len > 0 {
+            self.is_started = true;
+            self.is_moved = false;
+        } else if result.has_more && fitting > 0 {
+            self.is_moved = true;
+        }
+
         // Remove the rendered data from self.words so that we don’t render it again on the next
         // call to render.
         while rendered_len > 0 && !self.words.is_empty() {
@@ -363,13 +1122,108 @@ impl Element for Paragraph {
 
         Ok(result)
     }
-}
 
-impl From<Vec<StyledString>> for Paragraph {
-    fn from(text: Vec<StyledString>) -> Paragraph {
-        Paragraph {
-            text,
-            ..Default::default()
+    fn content_width(&self, context: &Context, style: Style) -> Option<ContentWidth> {
+        let mut result = ContentWidth::default();
+        for word in self.get_remaining_words(style) {
+            let width = word.width(&context.font_cache);
+            result.min = result.min.max(width);
+            result.max += width;
+        }
+        Some(result)
+    }
+
+    fn content_height(&self, context: &Context, style: Style, width: Mm) -> Option<Mm> {
+        let words = self.get_remaining_words(style);
+        let words = words.iter().map(Into::into);
+        let lines = wrap::Wrapper::new(words, context, width).count();
+        Some(style.line_height(&context.font_cache) * lines as f64)
+    }
+}
+
+/// A footnote element with the height of its text and the height of its first line.
+type FootnoteElement = (Box<dyn Element>, Mm, Mm);
+
+/// Returns the footnotes referenced by the given lines, if footnotes are enabled for the given
+/// area.
+///
+/// For every footnote, this function returns the element for the footnote text, the height of the
+/// text and the height of its first line.  If there are no footnotes on the current page yet, the
+/// heights of the first footnote include the separator.
+fn get_footnotes(
+    context: &Context,
+    area: &render::Area<'_>,
+    style: Style,
+    lines: &[(Vec<style::StyledCow<'_>>, usize)],
+) -> Vec<Vec<FootnoteElement>> {
+    let footnote_area = area.footnote_area();
+    let mut is_first = footnote_area
+        .as_ref()
+        .map(|area| area.size().height == Mm(0.0))
+        .unwrap_or_default();
+    let mut footnotes = Vec::with_capacity(lines.len());
+    for (line, _) in lines {
+        let mut line_footnotes = Vec::new();
+        if let Some(footnote_area) = &footnote_area {
+            for s in line {
+                if let Some(footnote) = &s.footnote {
+                    let style = style.and(footnote.style());
+                    let mut p = Paragraph::default().with_orphans(1).with_widows(1);
+                    p.push_styled(s.s.as_ref(), style::Effect::Superscript);
+                    p.push(" ");
+                    for s in footnote.text() {
+                        p.push(s.clone());
+                    }
+
+                    let width = footnote_area.size().width;
+                    let mut height = p.content_height(context, style, width).unwrap_or_default();
+                    let mut min_height = style.line_height(&context.font_cache);
+                    if is_first {
+                        height += crate::FOOTNOTE_SEPARATOR;
+                        min_height += crate::FOOTNOTE_SEPARATOR;
+                        is_first = false;
+                    }
+                    line_footnotes.push((
+                        Box::new(p.styled(style)) as Box<dyn Element>,
+                        height,
+                        min_height,
+                    ));
+                }
+            }
+        }
+        footnotes.push(line_footnotes);
+    }
+    footnotes
+}
+
+/// Calculates the additional space that has to be inserted after every space of the given line so
+/// that it fills the given width.
+///
+/// Trailing spaces at the end of the line are ignored.
+fn get_word_spacing(context: &Context, line: &[style::StyledCow<'_>], width: Mm) -> Mm {
+    let mut line_width = Mm(0.0);
+    let mut spaces = 0;
+    for (i, s) in line.iter().enumerate() {
+        let text = if i + 1 == line.len() {
+            s.s.trim_end()
+        } else {
+            &s.s
+        };
+        line_width += s.style.str_width(&context.font_cache, text);
+        spaces += text.matches(' ').count();
+    }
+    if spaces > 0 && line_width < width {
+        (width - line_width) / spaces as f64
+    } else {
+        Mm(0.0)
+    }
+}
+
+impl From<Vec<StyledString>> for Paragraph {
+    fn from(text: Vec<StyledString>) -> Paragraph {
+        Paragraph {
+            text,
+            ..Default::default()
         }
     }
 }
@@ -390,6 +1244,591 @@ impl<T: Into<StyledString>> iter::FromIterator<T> for Paragraph {
     }
 }
 
+/// A heading that adds an entry to the document outline.
+///
+/// The outline, also known as bookmarks, is displayed by PDF viewers for navigating the document.
+/// When the heading is rendered, an entry with the title and level of the heading is added to the
+/// outline that points to the position of the heading.  The entries are nested according to their
+/// level:  A heading is a child of the last preceding heading with a lower level.  Level 1 is the
+/// top level.
+///
+/// By default, the heading is rendered as a [`Paragraph`][].  You can use any other element with
+/// [`with_element`][].
+///
+/// # Examples
+///
+/// ```
+/// use genpdf::{elements, style, Element as _};
+/// let mut layout = elements::LinearLayout::vertical();
+/// layout.push(elements::Heading::new("Introduction", 1).styled(style::Effect::Bold));
+/// layout.push(elements::Paragraph::new("Lorem ipsum"));
+/// layout.push(elements::Heading::new("Motivation", 2));
+/// layout.push(elements::Paragraph::new("Dolor sit amet"));
+/// layout.push(elements::Heading::with_element(
+///     elements::Text::new("Conclusion"),
+///     "Conclusion",
+///     1,
+/// ));
+/// ```
+///
+/// [`Paragraph`]: struct.Paragraph.html
+/// [`with_element`]: #method.with_element
+#[derive(Clone, Debug, Default)]
+pub struct Heading<E: Element = Paragraph> {
+    element: E,
+    title: String,
+    level: usize,
+    is_recorded: bool,
+    toc_entry: Option<(TocEntries, usize)>,
+}
+
+impl Heading<Paragraph> {
+    /// Creates a new heading with the given text and level that is rendered as a paragraph.
+    ///
+    /// The text is also used as the title of the outline entry.
+    pub fn new(text: impl Into<StyledString>, level: usize) -> Heading<Paragraph> {
+        let text = text.into();
+        let title = text.s.clone();
+        Heading::with_element(Paragraph::new(text), title, level)
+    }
+}
+
+impl<E: Element> Heading<E> {
+    /// Creates a new heading that renders the given element and adds an outline entry with the
+    /// given title and level.
+    pub fn with_element(element: E, title: impl Into<String>, level: usize) -> Heading<E> {
+        Heading {
+            element,
+            title: title.into(),
+            level,
+            is_recorded: false,
+            toc_entry: None,
+        }
+    }
+
+    /// Returns the title of this heading.
+    pub fn title(&self) -> &str {
+        &self.title
+    }
+
+    /// Returns the level of this heading.
+    pub fn level(&self) -> usize {
+        self.level
+    }
+}
+
+impl<E: Element> Element for Heading<E> {
+    fn render(
+        &mut self,
+        context: &Context,
+        area: render::Area<'_>,
+        style: Style,
+    ) -> Result<RenderResult, Error> {
+        let result = self.element.render(context, area.clone(), style)?;
+        // If nothing was rendered, the heading is moved to the next page.
+        if !self.is_recorded && (result.size != Size::default() || !result.has_more) {
+            area.add_outline_entry(self.title.clone(), self.level);
+            if let Some((entries, idx)) = &self.toc_entry {
+                entries.borrow_mut()[*idx].page = Some(area.page_number());
+            }
+            self.is_recorded = true;
+        }
+        Ok(result)
+    }
+
+    fn content_width(&self, context: &Context, style: Style) -> Option<ContentWidth> {
+        self.element.content_width(context, style)
+    }
+
+    fn content_height(&self, context: &Context, style: Style, width: Mm) -> Option<Mm> {
+        self.element.content_height(context, style, width)
+    }
+
+    fn keeps_with_next(&self) -> bool {
+        self.element.keeps_with_next()
+    }
+}
+
+/// Adds a named destination at the position of the wrapped element.
+///
+/// Named destinations can be used as the target of internal links, see [`Link::Destination`][].
+/// The destination points to the upper left corner of the area of the first page that the
+/// wrapped element is rendered on.
+///
+/// # Examples
+///
+/// Direct usage:
+/// ```
+/// use genpdf::{elements, style};
+/// let target = elements::Anchor::new(elements::Paragraph::new("Appendix"), "appendix");
+/// let link = elements::Paragraph::new(
+///     style::StyledString::from("see the appendix")
+///         .with_link(style::Link::Destination("appendix".to_owned())),
+/// );
+/// ```
+///
+/// Using [`Element::anchored`][]:
+/// ```
+/// use genpdf::{elements, Element as _};
+/// let target = elements::Paragraph::new("Appendix").anchored("appendix");
+/// ```
+///
+/// [`Link::Destination`]: ../style/enum.Link.html#variant.Destination
+/// [`Element::anchored`]: ../trait.Element.html#method.anchored
+#[derive(Clone, Debug, Default)]
+pub struct Anchor<E: Element> {
+    element: E,
+    name: String,
+    is_added: bool,
+}
+
+impl<E: Element> Anchor<E> {
+    /// Creates a new anchor with the given name that wraps the given element.
+    pub fn new(element: E, name: impl Into<String>) -> Anchor<E> {
+        Anchor {
+            element,
+            name: name.into(),
+            is_added: false,
+        }
+    }
+}
+
+impl<E: Element> Element for Anchor<E> {
+    fn render(
+        &mut self,
+        context: &Context,
+        area: render::Area<'_>,
+        style: Style,
+    ) -> Result<RenderResult, Error> {
+        let result = self.element.render(context, area.clone(), style)?;
+        // If nothing was rendered, the element is moved to the next page.
+        if !self.is_added && (result.size != Size::default() || !result.has_more) {
+            area.add_destination(self.name.clone());
+            self.is_added = true;
+        }
+        Ok(result)
+    }
+
+    fn content_width(&self, context: &Context, style: Style) -> Option<ContentWidth> {
+        self.element.content_width(context, style)
+    }
+
+    fn content_height(&self, context: &Context, style: Style, width: Mm) -> Option<Mm> {
+        self.element.content_height(context, style, width)
+    }
+
+    fn keeps_with_next(&self) -> bool {
+        self.element.keeps_with_next()
+    }
+}
+
+/// A table of contents that lists headings with their page numbers.
+///
+/// The headings listed in the table of contents have to be created with the [`heading`][] or
+/// [`heading_with_element`][] methods.  These methods register the heading with the table of
+/// contents and return a [`Heading`][] element that can be added to the document.  The entries
+/// are listed in the order of registration and indented according to their level.
+///
+/// As the headings are registered before the document is rendered, the table of contents knows
+/// its entries, and therefore its own length, when it is rendered, even if it is placed before
+/// the headings.  The page numbers are filled in after all elements have been rendered (see
+/// [`Area::defer`][]).  Each entry consists of the title of the heading, dot leaders and the
+/// right-aligned page number.
+///
+/// # Example
+///
+/// ```
+/// use genpdf::elements;
+/// let mut toc = elements::TableOfContents::new();
+/// let introduction = toc.heading("Introduction", 1);
+/// let motivation = toc.heading("Motivation", 2);
+///
+/// let mut layout = elements::LinearLayout::vertical();
+/// layout.push(toc);
+/// layout.push(elements::PageBreak::new());
+/// layout.push(introduction);
+/// layout.push(elements::Paragraph::new("Lorem ipsum"));
+/// layout.push(motivation);
+/// layout.push(elements::Paragraph::new("Dolor sit amet"));
+/// ```
+///
+/// [`heading`]: #method.heading
+/// [`heading_with_element`]: #method.heading_with_element
+/// [`Heading`]: struct.Heading.html
+/// [`Area::defer`]: ../render/struct.Area.html#method.defer
+#[derive(Clone, Debug)]
+pub struct TableOfContents {
+    entries: TocEntries,
+    indent: Mm,
+    render_idx: usize,
+}
+
+impl TableOfContents {
+    /// Creates a new empty table of contents.
+    pub fn new() -> TableOfContents {
+        TableOfContents {
+            entries: TocEntries::default(),
+            indent: Mm(5.0),
+            render_idx: 0,
+        }
+    }
+
+    /// Sets the indentation per heading level, 5 mm by default.
+    pub fn set_indent(&mut self, indent: impl Into<Mm>) {
+        self.indent = indent.into();
+    }
+
+    /// Sets the indentation per heading level and returns the table of contents.
+    pub fn with_indent(mut self, indent: impl Into<Mm>) -> Self {
+        self.set_indent(indent);
+        self
+    }
+
+    /// Registers a heading with the given text and level and returns the heading.
+    ///
+    /// See [`Heading::new`][] for more information.
+    ///
+    /// [`Heading::new`]: struct.Heading.html#method.new
+    pub fn heading(&mut self, text: impl Into<StyledString>, level: usize) -> Heading<Paragraph> {
+        self.register(Heading::new(text, level))
+    }
+
+    /// Registers a heading that renders the given element and returns the heading.
+    ///
+    /// See [`Heading::with_element`][] for more information.
+    ///
+    /// [`Heading::with_element`]: struct.Heading.html#method.with_element
+    pub fn heading_with_element<E: Element>(
+        &mut self,
+        element: E,
+        title: impl Into<String>,
+        level: usize,
+    ) -> Heading<E> {
+        self.register(Heading::with_element(element, title, level))
+    }
+
+    fn register<E: Element>(&mut self, mut heading: Heading<E>) -> Heading<E> {
+        let mut entries = self.entries.borrow_mut();
+        heading.toc_entry = Some((self.entries.clone(), entries.len()));
+        entries.push(TocEntry {
+            title: heading.title.clone(),
+            level: heading.level,
+            page: None,
+        });
+        heading
+    }
+
+    fn get_indent(&self, entry: &TocEntry) -> Mm {
+        self.indent * entry.level.saturating_sub(1) as f64
+    }
+}
+
+impl Element for TableOfContents {
+    fn render(
+        &mut self,
+        context: &Context,
+        mut area: render::Area<'_>,
+        style: Style,
+    ) -> Result<RenderResult, Error> {
+        let mut result = RenderResult::default();
+        let font_cache = &context.font_cache;
+        let line_height = style.line_height(font_cache);
+        let number_width = style.str_width(font_cache, " 0000");
+        let entries = self.entries.borrow();
+        while let Some(entry) = entries.get(self.render_idx) {
+            let indent = self.get_indent(entry);
+            let width = area.size().width - indent - number_width;
+            let words: Vec<_> =
+                wrap::Words::new(vec![StyledString::new(entry.title.clone(), style)]).collect();
+            let lines: Vec<_> = wrap::Wrapper::new(words.iter().map(Into::into), context, width)
+                .map(|(line, _)| line)
+                .collect();
+            let height = line_height * lines.len().max(1) as f64;
+            if height > area.size().height {
+                result.has_more = true;
+                break;
+            }
+
+            let mut line_width = Mm(0.0);
+            for (i, line) in lines.iter().enumerate() {
+                let position = Position::new(indent, line_height * i as f64);
+                let mut section = area
+                    .text_section(font_cache, position, style)
+                    .map_err(|()| {
+                        Error::new("Could not create text section", ErrorKind::Internal)
+                    })?;
+                line_width = Mm(0.0);
+                for s in line {
+                    section.print_str(&s.s, s.style)?;
+                    line_width += s.width(font_cache);
+                }
+            }
+
+            let mut last_line = area.clone();
+            last_line.add_offset(Position::new(0, height - line_height));
+            let leaders_start = indent + line_width + style.char_width(font_cache, ' ');
+            let leaders_end = area.size().width - number_width;
+            let dot_width = style.char_width(font_cache, '.');
+            if leaders_end > leaders_start && dot_width > Mm(0.0) {
+                let count = ((leaders_end - leaders_start).0 / dot_width.0) as usize;
+                let position = Position::new(leaders_end - dot_width * count as f64, 0);
+                last_line.print_str(font_cache, position, style, ".".repeat(count))?;
+            }
+
+            let entries = self.entries.clone();
+            let idx = self.render_idx;
+            last_line.defer(move |font_cache, area| {
+                if let Some(page) = entries.borrow()[idx].page {
+                    let page = page.to_string();
+                    let x = area.size().width - style.str_width(font_cache, &page);
+                    area.print_str(font_cache, Position::new(x, 0), style, page)?;
+                }
+                Ok(())
+            });
+
+            result.size = result
+                .size
+                .stack_vertical(Size::new(area.size().width, height));
+            area.add_offset(Position::new(0, height));
+            self.render_idx += 1;
+        }
+        Ok(result)
+    }
+
+    fn content_width(&self, context: &Context, style: Style) -> Option<ContentWidth> {
+        let number_width = style.str_width(&context.font_cache, " 0000");
+        let mut result = ContentWidth::default();
+        for entry in self.entries.borrow().iter().skip(self.render_idx) {
+            let indent = self.get_indent(entry) + number_width;
+            let title = Paragraph::new(StyledString::new(entry.title.clone(), style));
+            if let Some(width) = title.content_width(context, style) {
+                result.min = result.min.max(width.min + indent);
+                result.max = result.max.max(width.max + indent);
+            }
+        }
+        Some(result)
+    }
+
+    fn content_height(&self, context: &Context, style: Style, width: Mm) -> Option<Mm> {
+        let number_width = style.str_width(&context.font_cache, " 0000");
+        let line_height = style.line_height(&context.font_cache);
+        let mut height = Mm(0.0);
+        for entry in self.entries.borrow().iter().skip(self.render_idx) {
+            let width = width - self.get_indent(entry) - number_width;
+            let title = Paragraph::new(StyledString::new(entry.title.clone(), style));
+            height += title
+                .content_height(context, style, width)?
+                .max(line_height);
+        }
+        Some(height)
+    }
+}
+
+impl Default for TableOfContents {
+    fn default() -> TableOfContents {
+        TableOfContents::new()
+    }
+}
+
+/// The entries of a table of contents, shared between the table and the registered headings.
+type TocEntries = rc::Rc<cell::RefCell<Vec<TocEntry>>>;
+
+#[derive(Clone, Debug)]
+struct TocEntry {
+    title: String,
+    level: usize,
+    page: Option<usize>,
+}
+
+/// A shape, for example a rectangle or an ellipse.
+///
+/// The shape is drawn with the fill and stroke colors of its [`ShapeStyle`][], see
+/// [`set_style`][].  The points of polygons and paths are relative to the upper left corner of the
+/// shape, and the size of these shapes is determined by their rightmost and lowest point.  If the
+/// shape does not fit on the current page, it is moved to the next page.
+///
+/// # Example
+///
+/// ```
+/// use genpdf::{elements, style};
+/// let shape = elements::Shape::rounded_rectangle((40, 10), 2)
+///     .with_style(style::ShapeStyle::new().with_fill_color(style::Color::Rgb(200, 200, 255)))
+///     .aligned(elements::Alignment::Center);
+/// ```
+///
+/// [`ShapeStyle`]: ../style/struct.ShapeStyle.html
+/// [`set_style`]: #method.set_style
+#[derive(Clone, Debug)]
+pub struct Shape {
+    kind: ShapeKind,
+    size: Size,
+    style: style::ShapeStyle,
+    alignment: Alignment,
+}
+
+#[derive(Clone, Debug)]
+enum ShapeKind {
+    Rectangle,
+    RoundedRectangle(Mm),
+    Ellipse,
+    Polygon(Vec<Position>),
+    Path(render::Path),
+}
+
+impl Shape {
+    fn new(kind: ShapeKind, size: Size) -> Shape {
+        Shape {
+            kind,
+            size,
+            style: style::ShapeStyle::new(),
+            alignment: Alignment::default(),
+        }
+    }
+
+    /// Creates a new rectangle with the given size.
+    pub fn rectangle(size: impl Into<Size>) -> Shape {
+        Shape::new(ShapeKind::Rectangle, size.into())
+    }
+
+    /// Creates a new rectangle with rounded corners with the given size and corner radius.
+    pub fn rounded_rectangle(size: impl Into<Size>, radius: impl Into<Mm>) -> Shape {
+        Shape::new(ShapeKind::RoundedRectangle(radius.into()), size.into())
+    }
+
+    /// Creates a new ellipse with the given size.
+    pub fn ellipse(size: impl Into<Size>) -> Shape {
+        Shape::new(ShapeKind::Ellipse, size.into())
+    }
+
+    /// Creates a new closed polygon with the given points.
+    pub fn polygon(points: Vec<Position>) -> Shape {
+        let size = bounding_size(&points);
+        Shape::new(ShapeKind::Polygon(points), size)
+    }
+
+    /// Creates a new shape with the given path.
+    pub fn path(path: render::Path) -> Shape {
+        let size = bounding_size(&path.points());
+        Shape::new(ShapeKind::Path(path), size)
+    }
+
+    /// Returns the size of this shape.
+    pub fn size(&self) -> Size {
+        self.size
+    }
+
+    /// Sets the style of this shape.
+    pub fn set_style(&mut self, style: impl Into<style::ShapeStyle>) {
+        self.style = style.into();
+    }
+
+    /// Sets the style of this shape and returns the shape.
+    pub fn with_style(mut self, style: impl Into<style::ShapeStyle>) -> Shape {
+        self.set_style(style);
+        self
+    }
+
+    /// Sets the alignment of this shape within the provided area.
+    pub fn set_alignment(&mut self, alignment: Alignment) {
+        self.alignment = alignment;
+    }
+
+    /// Sets the alignment of this shape within the provided area and returns the shape.
+    pub fn aligned(mut self, alignment: Alignment) -> Shape {
+        self.set_alignment(alignment);
+        self
+    }
+
+    fn get_offset(&self, max_width: Mm) -> Mm {
+        match self.alignment {
+            Alignment::Left | Alignment::Justified => Mm::default(),
+            Alignment::Center => (max_width - self.size.width) / 2.0,
+            Alignment::Right => max_width - self.size.width,
+        }
+    }
+}
+
+impl Element for Shape {
+    fn render(
+        &mut self,
+        _context: &Context,
+        mut area: render::Area<'_>,
+        style: Style,
+    ) -> Result<RenderResult, Error> {
+        let mut result = RenderResult::default();
+        if self.size.height > area.size().height {
+            result.has_more = true;
+            return Ok(result);
+        }
+        area.add_offset(Position::new(
+            self.get_offset(area.size().width).max(Mm::default()),
+            0,
+        ));
+        let shape_style = inherit_shape_style(&self.style, style);
+        let origin = Position::default();
+        match &self.kind {
+            ShapeKind::Rectangle => area.draw_rectangle(origin, self.size, &shape_style),
+            ShapeKind::RoundedRectangle(radius) => {
+                area.draw_rounded_rectangle(origin, self.size, *radius, &shape_style)
+            }
+            ShapeKind::Ellipse => area.draw_ellipse(origin, self.size, &shape_style),
+            ShapeKind::Polygon(points) => area.draw_polygon(points.clone(), &shape_style),
+            ShapeKind::Path(path) => area.draw_path(path, &shape_style),
+        }
+        result.size = self.size;
+        Ok(result)
+    }
+
+    fn content_width(&self, _context: &Context, _style: Style) -> Option<ContentWidth> {
+        Some(ContentWidth::fixed(self.size.width))
+    }
+
+    fn content_height(&self, _context: &Context, _style: Style, _width: Mm) -> Option<Mm> {
+        Some(self.size.height)
+    }
+}
+
+/// Returns a copy of the given line style that uses the color, the stroke opacity and the blend
+/// mode of the given style if the line style does not have a color, is opaque or uses the normal
+/// blend mode.
+fn inherit_line_style(line_style: &style::LineStyle, style: Style) -> style::LineStyle {
+    let mut line_style = line_style.clone();
+    if line_style.color().is_none() {
+        line_style.set_color(style.color());
+    }
+    if line_style.opacity() >= 1.0 {
+        line_style.set_opacity(style.stroke_opacity());
+    }
+    if line_style.blend_mode() == style::BlendMode::Normal {
+        line_style.set_blend_mode(style.blend_mode());
+    }
+    line_style
+}
+
+/// Returns a copy of the given shape style that uses the fill opacity and the blend mode of the
+/// given style if the shape style is opaque or uses the normal blend mode.  The line style is
+/// inherited with `inherit_line_style`.
+fn inherit_shape_style(shape_style: &style::ShapeStyle, style: Style) -> style::ShapeStyle {
+    let mut shape_style = shape_style.clone();
+    if shape_style.fill_opacity() >= 1.0 {
+        shape_style.set_fill_opacity(style.fill_opacity());
+    }
+    if shape_style.blend_mode() == style::BlendMode::Normal {
+        shape_style.set_blend_mode(style.blend_mode());
+    }
+    let line_style = shape_style
+        .line_style()
+        .map(|line_style| inherit_line_style(line_style, style));
+    shape_style.with_line_style(line_style)
+}
+
+/// Returns the size of the rectangle from the origin to the rightmost and lowest point.
+fn bounding_size(points: &[Position]) -> Size {
+    points.iter().fold(Size::default(), |size, point| {
+        Size::new(size.width.max(point.x), size.height.max(point.y))
+    })
+}
+
 /// A line break.
 ///
 /// This element inserts a given number of empty lines.
@@ -436,19 +1875,38 @@ impl Element for Break {
         }
         Ok(result)
     }
+
+    fn content_width(&self, _context: &Context, _style: Style) -> Option<ContentWidth> {
+        Some(ContentWidth::default())
+    }
+
+    fn content_height(&self, context: &Context, style: Style, _width: Mm) -> Option<Mm> {
+        Some(style.line_height(&context.font_cache) * self.lines.max(0.0))
+    }
 }
 
 /// A page break.
 ///
-/// This element inserts a page break.
+/// This element inserts a page break.  If a page size is set, the following pages use this size,
+/// see [`Area::set_next_page_size`][].  This can be used to switch to landscape orientation, for
+/// example for wide tables.
 ///
-/// # Example
+/// # Examples
+///
+/// ```
+/// let pb = genpdf::elements::PageBreak::new();
+/// ```
 ///
+/// Switching to landscape orientation:
 /// ```
-/// let pb = genpdf::elements::PageBreak::new();
+/// use genpdf::{elements, PaperSize};
+/// let pb = elements::PageBreak::new().with_size(PaperSize::A4.landscape());
 /// ```
+///
+/// [`Area::set_next_page_size`]: ../render/struct.Area.html#method.set_next_page_size
 #[derive(Clone, Copy, Debug, Default)]
 pub struct PageBreak {
+    size: Option<Size>,
     cont: bool,
 }
 
@@ -457,18 +1915,32 @@ impl PageBreak {
     pub fn new() -> PageBreak {
         PageBreak::default()
     }
+
+    /// Sets the size of the pages after this page break.
+    pub fn set_size(&mut self, size: impl Into<Size>) {
+        self.size = Some(size.into());
+    }
+
+    /// Sets the size of the pages after this page break and returns the page break.
+    pub fn with_size(mut self, size: impl Into<Size>) -> Self {
+        self.set_size(size);
+        self
+    }
 }
 
 impl Element for PageBreak {
     fn render(
         &mut self,
         _context: &Context,
-        _area: render::Area<'_>,
+        area: render::Area<'_>,
         _style: Style,
     ) -> Result<RenderResult, Error> {
         if self.cont {
             Ok(RenderResult::default())
         } else {
+            if let Some(size) = self.size {
+                area.set_next_page_size(size);
+            }
             // We don’t use (0,0) as the size as this might abort the render process if this is the
             // first element on a new page, see the Rendering Process section of the crate
             // documentation.
@@ -479,6 +1951,12 @@ impl Element for PageBreak {
             })
         }
     }
+
+    // The height of a page break depends on the remaining space on the page, so we only report
+    // its width.
+    fn content_width(&self, _context: &Context, _style: Style) -> Option<ContentWidth> {
+        Some(ContentWidth::default())
+    }
 }
 
 /// Adds a padding to the wrapped element.
@@ -534,6 +2012,21 @@ impl<E: Element> Element for PaddedElement<E> {
         result.size.height += self.padding.top + self.padding.bottom;
         Ok(result)
     }
+
+    fn content_width(&self, context: &Context, style: Style) -> Option<ContentWidth> {
+        let content_width = self.element.content_width(context, style)?;
+        Some(content_width + self.padding.left + self.padding.right)
+    }
+
+    fn content_height(&self, context: &Context, style: Style, width: Mm) -> Option<Mm> {
+        let width = width - self.padding.left - self.padding.right;
+        let height = self.element.content_height(context, style, width)?;
+        Some(height + self.padding.top + self.padding.bottom)
+    }
+
+    fn keeps_with_next(&self) -> bool {
+        self.element.keeps_with_next()
+    }
 }
 
 /// Adds a default style to the wrapped element and its children.
@@ -583,10 +2076,27 @@ impl<E: Element> Element for StyledElement<E> {
         style.merge(self.style);
         self.element.render(context, area, style)
     }
+
+    fn content_width(&self, context: &Context, mut style: Style) -> Option<ContentWidth> {
+        style.merge(self.style);
+        self.element.content_width(context, style)
+    }
+
+    fn content_height(&self, context: &Context, mut style: Style, width: Mm) -> Option<Mm> {
+        style.merge(self.style);
+        self.element.content_height(context, style, width)
+    }
+
+    fn keeps_with_next(&self) -> bool {
+        self.element.keeps_with_next()
+    }
 }
 
 /// Adds a frame around the wrapped element.
 ///
+/// The frame is drawn with the [`LineStyle`][] set with [`set_line_style`][].  If the line style
+/// has no color, the color of the current style is used.
+///
 /// # Examples
 ///
 /// Direct usage:
@@ -597,6 +2107,15 @@ impl<E: Element> Element for StyledElement<E> {
 /// );
 /// ```
 ///
+/// With a custom line style:
+/// ```
+/// use genpdf::{elements, style};
+/// let p = elements::FramedElement::with_line_style(
+///     elements::Paragraph::new("text"),
+///     style::LineStyle::new().with_thickness(1).with_join(style::LineJoin::Round),
+/// );
+/// ```
+///
 /// Using [`Element::framed`][]:
 /// ```
 /// use genpdf::{elements, Element as _};
@@ -604,59 +2123,578 @@ impl<E: Element> Element for StyledElement<E> {
 /// ```
 ///
 /// [`Element::framed`]: ../trait.Element.html#method.framed
+/// [`LineStyle`]: ../style/struct.LineStyle.html
+/// [`set_line_style`]: #method.set_line_style
 #[derive(Clone, Debug, Default)]
 pub struct FramedElement<E: Element> {
     element: E,
+    line_style: style::LineStyle,
     is_first: bool,
 }
 
 impl<E: Element> FramedElement<E> {
     /// Creates a new framed element that wraps the given element.
     pub fn new(element: E) -> FramedElement<E> {
+        FramedElement::with_line_style(element, style::LineStyle::new())
+    }
+
+    /// Creates a new framed element that wraps the given element and draws the frame with the
+    /// given line style.
+    pub fn with_line_style(
+        element: E,
+        line_style: impl Into<style::LineStyle>,
+    ) -> FramedElement<E> {
         FramedElement {
             element,
+            line_style: line_style.into(),
+            is_first: true,
+        }
+    }
+
+    /// Sets the line style of the frame.
+    pub fn set_line_style(&mut self, line_style: impl Into<style::LineStyle>) {
+        self.line_style = line_style.into();
+    }
+}
+
+impl<E: Element> Element for FramedElement<E> {
+    fn render(
+        &mut self,
+        context: &Context,
+        area: render::Area<'_>,
+        style: Style,
+    ) -> Result<RenderResult, Error> {
+        let result = self.element.render(context, area.clone(), style)?;
+        if result.size == Size::default() && result.has_more {
+            // The element was moved to the next page, so we don't draw the frame yet.
+            return Ok(result);
+        }
+        let line_style = inherit_line_style(&self.line_style, style);
+        area.draw_line(
+            vec![Position::default(), Position::new(0, result.size.height)],
+            line_style.clone(),
+        );
+        area.draw_line(
+            vec![
+                Position::new(area.size().width, 0),
+                Position::new(area.size().width, result.size.height),
+            ],
+            line_style.clone(),
+        );
+        if self.is_first {
+            area.draw_line(
+                vec![Position::default(), Position::new(area.size().width, 0)],
+                line_style.clone(),
+            );
+        }
+        if !result.has_more {
+            area.draw_line(
+                vec![
+                    Position::new(0, result.size.height),
+                    Position::new(area.size().width, result.size.height),
+                ],
+                line_style,
+            );
+        }
+        self.is_first = false;
+        Ok(result)
+    }
+
+    fn content_width(&self, context: &Context, style: Style) -> Option<ContentWidth> {
+        self.element.content_width(context, style)
+    }
+
+    fn content_height(&self, context: &Context, style: Style, width: Mm) -> Option<Mm> {
+        self.element.content_height(context, style, width)
+    }
+
+    fn keeps_with_next(&self) -> bool {
+        self.element.keeps_with_next()
+    }
+}
+
+/// Draws a box with a background and borders around the wrapped element.
+///
+/// The box is decorated with the given [`BoxDecoration`][]:  The background is drawn beneath the
+/// content of the wrapped element, see [`Area::defer_background`][], and the borders are drawn
+/// above it.  Borders without a color use the color of the current style.  If the element is
+/// continued on the next page, the bottom border and the rounded bottom corners are only drawn on
+/// the last page and the top border and the rounded top corners only on the first page.
+///
+/// The box uses the full width of the provided area.  Use [`Element::padded`][] to add a padding
+/// between the box and the content.
+///
+/// # Examples
+///
+/// Direct usage:
+/// ```
+/// use genpdf::{elements, style};
+/// let p = elements::DecoratedElement::new(
+///     elements::Paragraph::new("text"),
+///     style::BoxDecoration::new()
+///         .with_background_color(style::Color::Rgb(230, 230, 230))
+///         .with_border(style::LineStyle::new())
+///         .with_corner_radius(2),
+/// );
+/// ```
+///
+/// Using [`Element::decorated`][]:
+/// ```
+/// use genpdf::{elements, style, Element as _};
+/// let p = elements::Paragraph::new("text")
+///     .padded(2)
+///     .decorated(style::Color::Rgb(230, 230, 230));
+/// ```
+///
+/// [`BoxDecoration`]: ../style/struct.BoxDecoration.html
+/// [`Area::defer_background`]: ../render/struct.Area.html#method.defer_background
+/// [`Element::padded`]: ../trait.Element.html#method.padded
+/// [`Element::decorated`]: ../trait.Element.html#method.decorated
+#[derive(Clone, Debug)]
+pub struct DecoratedElement<E: Element> {
+    element: E,
+    decoration: style::BoxDecoration,
+    is_first: bool,
+}
+
+impl<E: Element> DecoratedElement<E> {
+    /// Creates a new decorated element that wraps the given element.
+    pub fn new(element: E, decoration: impl Into<style::BoxDecoration>) -> DecoratedElement<E> {
+        DecoratedElement {
+            element,
+            decoration: decoration.into(),
             is_first: true,
         }
     }
 }
 
-impl<E: Element> Element for FramedElement<E> {
+impl<E: Element> Element for DecoratedElement<E> {
+    fn render(
+        &mut self,
+        context: &Context,
+        area: render::Area<'_>,
+        style: Style,
+    ) -> Result<RenderResult, Error> {
+        let result = self.element.render(context, area.clone(), style)?;
+        if result.size == Size::default() && result.has_more {
+            // The element was moved to the next page, so we don't draw the box yet.
+            return Ok(result);
+        }
+        let size = Size::new(area.size().width, result.size.height);
+        let is_last = !result.has_more;
+        let decoration_box = DecorationBox::new(size, &self.decoration, self.is_first, is_last);
+
+        if let Some(color) = self.decoration.background_color() {
+            let path = decoration_box.path();
+            area.defer_background(move |_, area| {
+                let shape_style = style::ShapeStyle::new()
+                    .with_fill_color(color)
+                    .with_stroke_color(None);
+                area.draw_path(&path, &inherit_shape_style(&shape_style, style));
+                Ok(())
+            });
+        }
+
+        let borders = [
+            self.decoration.top_border().filter(|_| self.is_first),
+            self.decoration.right_border(),
+            self.decoration.bottom_border().filter(|_| is_last),
+            self.decoration.left_border(),
+        ];
+        if borders
+            .iter()
+            .all(|border| border.is_some() && *border == borders[0])
+        {
+            let shape_style = style::ShapeStyle::new().with_line_style(borders[0].cloned());
+            area.draw_path(
+                &decoration_box.path(),
+                &inherit_shape_style(&shape_style, style),
+            );
+        } else {
+            for (side, border) in borders.iter().enumerate() {
+                if let Some(border) = border {
+                    let path = decoration_box.side_path(side, &borders);
+                    let shape_style = style::ShapeStyle::new().with_line_style((*border).clone());
+                    area.draw_path(&path, &inherit_shape_style(&shape_style, style));
+                }
+            }
+        }
+
+        self.is_first = false;
+        Ok(result)
+    }
+
+    fn content_width(&self, context: &Context, style: Style) -> Option<ContentWidth> {
+        self.element.content_width(context, style)
+    }
+
+    fn content_height(&self, context: &Context, style: Style, width: Mm) -> Option<Mm> {
+        self.element.content_height(context, style, width)
+    }
+
+    fn keeps_with_next(&self) -> bool {
+        self.element.keeps_with_next()
+    }
+}
+
+/// The geometry of the box drawn by a [`DecoratedElement`][].
+///
+/// The corners and sides are numbered clockwise, starting with the upper left corner and the top
+/// side, so that side `i` connects the corners `i` and `i + 1`.
+///
+/// [`DecoratedElement`]: struct.DecoratedElement.html
+struct DecorationBox {
+    // the corner points of the box
+    corners: [Position; 4],
+    // the radii of the corners
+    radii: [Mm; 4],
+}
+
+impl DecorationBox {
+    fn new(
+        size: Size,
+        decoration: &style::BoxDecoration,
+        is_first: bool,
+        is_last: bool,
+    ) -> DecorationBox {
+        let radius = decoration
+            .corner_radius()
+            .min(size.width / 2.0)
+            .min(size.height / 2.0)
+            .max(Mm(0.0));
+        let top = if is_first { radius } else { Mm(0.0) };
+        let bottom = if is_last { radius } else { Mm(0.0) };
+        DecorationBox {
+            corners: [
+                Position::default(),
+                Position::new(size.width, 0),
+                Position::new(size.width, size.height),
+                Position::new(0, size.height),
+            ],
+            radii: [top, top, bottom, bottom],
+        }
+    }
+
+    /// Returns the start and the end point of the arc of the given corner, in clockwise
+    /// direction.
+    fn arc(&self, corner: usize) -> (Position, Position) {
+        let (point, radius) = (self.corners[corner], self.radii[corner]);
+        let (x, y) = (Position::new(radius, 0), Position::new(0, radius));
+        match corner {
+            0 => (point + y, point + x),
+            1 => (point - x, point + y),
+            2 => (point - y, point - x),
+            _ => (point + x, point - y),
+        }
+    }
+
+    /// Adds the arc of the given corner to the given path.
+    fn push_arc(&self, path: &mut render::Path, corner: usize) {
+        if self.radii[corner] > Mm(0.0) {
+            path.push_arc(self.corners[corner], self.arc(corner).1);
+        }
+    }
+
+    /// Returns the closed outline of the box.
+    fn path(&self) -> render::Path {
+        let mut path = render::Path::new(self.arc(0).1);
+        for corner in 1..=4 {
+            path.push_line(self.arc(corner % 4).0);
+            self.push_arc(&mut path, corner % 4);
+        }
+        path.closed()
+    }
+
+    /// Returns the path of the given side, including the arcs of the corners that belong to the
+    /// side.  A corner belongs to the horizontal side if it has a border, otherwise to the
+    /// vertical side.
+    fn side_path(&self, side: usize, borders: &[Option<&style::LineStyle>; 4]) -> render::Path {
+        let owner = |corner: usize| {
+            let (horizontal, vertical) = match corner {
+                0 => (0, 3),
+                1 => (0, 1),
+                2 => (2, 1),
+                _ => (2, 3),
+            };
+            if borders[horizontal].is_some() {
+                horizontal
+            } else {
+                vertical
+            }
+        };
+        let (start, end) = (side, (side + 1) % 4);
+        let mut path = if owner(start) == side {
+            let mut path = render::Path::new(self.arc(start).0);
+            self.push_arc(&mut path, start);
+            path
+        } else {
+            render::Path::new(self.arc(start).1)
+        };
+        path.push_line(self.arc(end).0);
+        if owner(end) == side {
+            self.push_arc(&mut path, end);
+        }
+        path
+    }
+}
+
+/// Moves the wrapped element to the next page if it would be split across pages.
+///
+/// Before the wrapped element is rendered for the first time, its height is measured with
+/// [`Element::content_height`][].  If it does not fit into the provided area, it is moved to the
+/// next page.  The element is only moved once:  If it does not fit on the next page either, it is
+/// split across pages as usual.  If the content height of the element is unknown, it is never
+/// moved.
+///
+/// # Examples
+///
+/// Direct usage:
+/// ```
+/// use genpdf::elements;
+/// let p = elements::KeepTogether::new(
+///     elements::Paragraph::new("text"),
+/// );
+/// ```
+///
+/// Using [`Element::keep_together`][]:
+/// ```
+/// use genpdf::{elements, Element as _};
+/// let p = elements::Paragraph::new("text").keep_together();
+/// ```
+///
+/// [`Element::content_height`]: ../trait.Element.html#method.content_height
+/// [`Element::keep_together`]: ../trait.Element.html#method.keep_together
+#[derive(Clone, Debug, Default)]
+pub struct KeepTogether<E: Element> {
+    element: E,
+    is_checked: bool,
+}
+
+impl<E: Element> KeepTogether<E> {
+    /// Creates a new wrapper that keeps the given element together.
+    pub fn new(element: E) -> KeepTogether<E> {
+        KeepTogether {
+            element,
+            is_checked: false,
+        }
+    }
+}
+
+impl<E: Element> Element for KeepTogether<E> {
+    fn render(
+        &mut self,
+        context: &Context,
+        area: render::Area<'_>,
+        style: Style,
+    ) -> Result<RenderResult, Error> {
+        if !self.is_checked {
+            self.is_checked = true;
+            let height = self
+                .element
+                .content_height(context, style, area.size().width);
+            if height.map(|h| h > area.size().height).unwrap_or_default() {
+                return Ok(RenderResult {
+                    size: Size::default(),
+                    has_more: true,
+                });
+            }
+        }
+        self.element.render(context, area, style)
+    }
+
+    fn content_width(&self, context: &Context, style: Style) -> Option<ContentWidth> {
+        self.element.content_width(context, style)
+    }
+
+    fn content_height(&self, context: &Context, style: Style, width: Mm) -> Option<Mm> {
+        self.element.content_height(context, style, width)
+    }
+
+    fn keeps_with_next(&self) -> bool {
+        self.element.keeps_with_next()
+    }
+}
+
+/// Keeps the wrapped element on the same page as the next element.
+///
+/// If the wrapped element is part of a vertical [`LinearLayout`][], it is moved to the next page
+/// if it does not fit on the current page together with the first line of the next element, see
+/// [`Element::keeps_with_next`][].  This is typically used for headings.
+///
+/// # Examples
+///
+/// Direct usage:
+/// ```
+/// use genpdf::elements;
+/// let layout = elements::LinearLayout::vertical()
+///     .element(elements::KeepWithNext::new(elements::Heading::new("Heading", 1)))
+///     .element(elements::Paragraph::new("text"));
+/// ```
+///
+/// Using [`Element::keep_with_next`][]:
+/// ```
+/// use genpdf::{elements, Element as _};
+/// let layout = elements::LinearLayout::vertical()
+///     .element(elements::Heading::new("Heading", 1).keep_with_next())
+///     .element(elements::Paragraph::new("text"));
+/// ```
+///
+/// [`LinearLayout`]: struct.LinearLayout.html
+/// [`Element::keeps_with_next`]: ../trait.Element.html#method.keeps_with_next
+/// [`Element::keep_with_next`]: ../trait.Element.html#method.keep_with_next
+#[derive(Clone, Debug, Default)]
+pub struct KeepWithNext<E: Element> {
+    element: E,
+}
+
+impl<E: Element> KeepWithNext<E> {
+    /// Creates a new wrapper that keeps the given element with the next element.
+    pub fn new(element: E) -> KeepWithNext<E> {
+        KeepWithNext { element }
+    }
+}
+
+impl<E: Element> Element for KeepWithNext<E> {
+    fn render(
+        &mut self,
+        context: &Context,
+        area: render::Area<'_>,
+        style: Style,
+    ) -> Result<RenderResult, Error> {
+        self.element.render(context, area, style)
+    }
+
+    fn content_width(&self, context: &Context, style: Style) -> Option<ContentWidth> {
+        self.element.content_width(context, style)
+    }
+
+    fn content_height(&self, context: &Context, style: Style, width: Mm) -> Option<Mm> {
+        self.element.content_height(context, style, width)
+    }
+
+    fn keeps_with_next(&self) -> bool {
+        true
+    }
+}
+
+/// Rotates, scales or skews the wrapped element.
+///
+/// The wrapped element is rendered in an area with its measured size, see
+/// [`Element::content_width`][] and [`Element::content_height`][], and then transformed around the
+/// center of this area.  The transformed element takes up the space of the bounding box of the
+/// transformed area.  The width of the wrapped element is limited so that the transformed element
+/// fits into the available width, if possible.  The wrapped element must provide a content height,
+/// and it is not split across pages.
+///
+/// Links and destinations in the wrapped element are not transformed.
+///
+/// # Examples
+///
+/// A vertical caption:
+/// ```
+/// use genpdf::{elements, render};
+/// let caption = elements::Transformed::new(
+///     elements::Text::new("Figure 1"),
+///     render::Transform::rotate(90.0),
+/// );
+/// ```
+///
+/// A scaled-down preview using [`Element::transformed`][]:
+/// ```
+/// use genpdf::{elements, render, Element as _};
+/// let preview = elements::Paragraph::new("Lorem ipsum dolor sit amet")
+///     .framed()
+///     .transformed(render::Transform::scale(0.5, 0.5));
+/// ```
+///
+/// [`Element::content_width`]: ../trait.Element.html#method.content_width
+/// [`Element::content_height`]: ../trait.Element.html#method.content_height
+/// [`Element::transformed`]: ../trait.Element.html#method.transformed
+#[derive(Clone, Debug)]
+pub struct Transformed<E: Element> {
+    element: E,
+    transform: render::Transform,
+}
+
+impl<E: Element> Transformed<E> {
+    /// Creates a new element that applies the given transformation to the given element.
+    pub fn new(element: E, transform: render::Transform) -> Transformed<E> {
+        Transformed { element, transform }
+    }
+
+    /// Returns the width of the wrapped element for the given available width.
+    fn element_width(&self, context: &Context, style: Style, available_width: Mm) -> Mm {
+        let content_width = self
+            .element
+            .content_width(context, style)
+            .map(|width| width.max);
+        let factor = self.transform.bounding_size(Size::new(1, 0)).width;
+        if factor > Mm(1e-6) {
+            let max_width = available_width / factor.0;
+            content_width.map_or(max_width, |width| width.min(max_width))
+        } else {
+            content_width.unwrap_or(available_width)
+        }
+    }
+
+    /// Returns the size of the wrapped element for the given available width.
+    fn element_size(&self, context: &Context, style: Style, available_width: Mm) -> Option<Size> {
+        let width = self.element_width(context, style, available_width);
+        let height = self.element.content_height(context, style, width)?;
+        Some(Size::new(width, height))
+    }
+}
+
+impl<E: Element> Element for Transformed<E> {
     fn render(
         &mut self,
         context: &Context,
-        area: render::Area<'_>,
+        mut area: render::Area<'_>,
         style: Style,
     ) -> Result<RenderResult, Error> {
-        let result = self.element.render(context, area.clone(), style)?;
-        area.draw_line(
-            vec![Position::default(), Position::new(0, result.size.height)],
-            style,
-        );
-        area.draw_line(
-            vec![
-                Position::new(area.size().width, 0),
-                Position::new(area.size().width, result.size.height),
-            ],
-            style,
-        );
-        if self.is_first {
-            area.draw_line(
-                vec![Position::default(), Position::new(area.size().width, 0)],
-                style,
-            );
+        let mut result = RenderResult::default();
+        let size = self
+            .element_size(context, style, area.size().width)
+            .ok_or_else(|| {
+                Error::new(
+                    "Could not measure the height of a transformed element",
+                    ErrorKind::InvalidData,
+                )
+            })?;
+        let bounding_size = self.transform.bounding_size(size);
+        if bounding_size.height > area.size().height {
+            result.has_more = true;
+            return Ok(result);
         }
-        if !result.has_more {
-            area.draw_line(
-                vec![
-                    Position::new(0, result.size.height),
-                    Position::new(area.size().width, result.size.height),
-                ],
-                style,
-            );
+
+        let center = Position::new(bounding_size.width / 2.0, bounding_size.height / 2.0);
+        area.add_offset(center - Position::new(size.width / 2.0, size.height / 2.0));
+        area.set_size(size);
+        let center = Position::new(size.width / 2.0, size.height / 2.0);
+        let element_result = area.transformed(center, self.transform, |area| {
+            self.element.render(context, area.clone(), style)
+        })?;
+        if element_result.has_more {
+            return Err(Error::new(
+                "A transformed element does not fit into its measured size",
+                ErrorKind::InvalidData,
+            ));
         }
-        self.is_first = false;
+        result.size = bounding_size;
         Ok(result)
     }
+
+    fn content_width(&self, context: &Context, style: Style) -> Option<ContentWidth> {
+        let width = self.element.content_width(context, style)?.max;
+        let height = self.element.content_height(context, style, width)?;
+        let width = self.transform.bounding_size(Size::new(width, height)).width;
+        Some(ContentWidth::fixed(width))
+    }
+
+    fn content_height(&self, context: &Context, style: Style, width: Mm) -> Option<Mm> {
+        let size = self.element_size(context, style, width)?;
+        Some(self.transform.bounding_size(size).height)
+    }
 }
 
 /// An unordered list of elements with bullet points.
@@ -736,6 +2774,14 @@ impl Element for UnorderedList {
     ) -> Result<RenderResult, Error> {
         self.layout.render(context, area, style)
     }
+
+    fn content_width(&self, context: &Context, style: Style) -> Option<ContentWidth> {
+        self.layout.content_width(context, style)
+    }
+
+    fn content_height(&self, context: &Context, style: Style, width: Mm) -> Option<Mm> {
+        self.layout.content_height(context, style, width)
+    }
 }
 
 impl Default for UnorderedList {
@@ -817,6 +2863,14 @@ impl Element for OrderedList {
     ) -> Result<RenderResult, Error> {
         self.layout.render(context, area, style)
     }
+
+    fn content_width(&self, context: &Context, style: Style) -> Option<ContentWidth> {
+        self.layout.content_width(context, style)
+    }
+
+    fn content_height(&self, context: &Context, style: Style, width: Mm) -> Option<Mm> {
+        self.layout.content_height(context, style, width)
+    }
 }
 
 impl Default for OrderedList {
@@ -898,6 +2952,17 @@ impl<E: Element> Element for BulletPoint<E> {
         }
         Ok(result)
     }
+
+    fn content_width(&self, context: &Context, style: Style) -> Option<ContentWidth> {
+        self.element
+            .content_width(context, style)
+            .map(|width| width + self.indent)
+    }
+
+    fn content_height(&self, context: &Context, style: Style, width: Mm) -> Option<Mm> {
+        self.element
+            .content_height(context, style, width - self.indent)
+    }
 }
 
 /// A decorator for table cells.
@@ -916,10 +2981,15 @@ pub trait CellDecorator {
     }
 
     /// Styles the cell with the given indizes thas has been rendered within the given area.
+    ///
+    /// The span is the number of columns and rows covered by the cell, see [`CellSpan`][].
+    ///
+    /// [`CellSpan`]: struct.CellSpan.html
     fn decorate_cell(
         &mut self,
         column: usize,
         row: usize,
+        span: CellSpan,
         has_more: bool,
         area: render::Area<'_>,
         style: Style,
@@ -932,15 +3002,41 @@ pub trait CellDecorator {
 /// inner, outer and continuation borders are drawn.  A continuation border is a border between a
 /// cell and the page margin that occurs if a cell has to be wrapped to a new page.
 ///
+/// The borders are drawn with the [`LineStyle`][] set with [`set_line_style`][].  The outer
+/// borders can use a different line style, see [`set_outer_line_style`][].  If a line style has
+/// no color, the color of the current style is used.
+///
+/// # Example
+///
+/// ```
+/// use genpdf::{elements, style};
+/// // thin inner lines and thick outer borders
+/// let decorator = elements::FrameCellDecorator::new(true, true, false)
+///     .with_line_style(style::LineStyle::new().with_thickness(0.1))
+///     .with_outer_line_style(
+///         style::LineStyle::new()
+///             .with_thickness(0.8)
+///             .with_cap(style::LineCap::Square),
+///     );
+/// let mut table = elements::TableLayout::new(vec![1, 1]);
+/// table.set_cell_decorator(decorator);
+/// ```
+///
 /// [`TableLayout`]: struct.TableLayout.html
+/// [`LineStyle`]: ../style/struct.LineStyle.html
+/// [`set_line_style`]: #method.set_line_style
+/// [`set_outer_line_style`]: #method.set_outer_line_style
 #[derive(Clone, Debug, Default)]
 pub struct FrameCellDecorator {
     inner: bool,
     outer: bool,
     cont: bool,
+    line_style: style::LineStyle,
+    outer_line_style: Option<style::LineStyle>,
     num_columns: usize,
     num_rows: usize,
-    last_row: Option<usize>,
+    // cells that have been rendered with has_more == true and will be continued
+    continued: collections::HashSet<(usize, usize)>,
 }
 
 impl FrameCellDecorator {
@@ -955,6 +3051,42 @@ impl FrameCellDecorator {
         }
     }
 
+    /// Sets the line style of the borders.
+    ///
+    /// This line style is also used for the outer borders unless an outer line style has been
+    /// set with [`set_outer_line_style`][].
+    ///
+    /// [`set_outer_line_style`]: #method.set_outer_line_style
+    pub fn set_line_style(&mut self, line_style: impl Into<style::LineStyle>) {
+        self.line_style = line_style.into();
+    }
+
+    /// Sets the line style of the borders and returns the decorator.
+    pub fn with_line_style(mut self, line_style: impl Into<style::LineStyle>) -> Self {
+        self.set_line_style(line_style);
+        self
+    }
+
+    /// Sets the line style of the outer borders of the table.
+    pub fn set_outer_line_style(&mut self, line_style: impl Into<style::LineStyle>) {
+        self.outer_line_style = Some(line_style.into());
+    }
+
+    /// Sets the line style of the outer borders of the table and returns the decorator.
+    pub fn with_outer_line_style(mut self, line_style: impl Into<style::LineStyle>) -> Self {
+        self.set_outer_line_style(line_style);
+        self
+    }
+
+    fn get_line_style(&self, is_outer: bool, style: Style) -> style::LineStyle {
+        let line_style = if is_outer {
+            self.outer_line_style.as_ref().unwrap_or(&self.line_style)
+        } else {
+            &self.line_style
+        };
+        inherit_line_style(line_style, style)
+    }
+
     fn print_left(&self, column: usize) -> bool {
         if column == 0 {
             self.outer
@@ -971,8 +3103,8 @@ impl FrameCellDecorator {
         }
     }
 
-    fn print_top(&self, row: usize) -> bool {
-        if self.last_row.map(|last_row| row > last_row).unwrap_or(true) {
+    fn print_top(&self, row: usize, is_continued: bool) -> bool {
+        if !is_continued {
             if row == 0 {
                 self.outer
             } else {
@@ -1004,116 +3136,477 @@ impl CellDecorator for FrameCellDecorator {
         &mut self,
         column: usize,
         row: usize,
+        span: CellSpan,
         has_more: bool,
         area: render::Area<'_>,
         style: Style,
     ) {
         let size = area.size();
+        let is_continued = if has_more {
+            !self.continued.insert((column, row))
+        } else {
+            self.continued.remove(&(column, row))
+        };
+        let last_column = column + span.columns.max(1) - 1;
+        let last_row = row + span.rows.max(1) - 1;
 
         if self.print_left(column) {
             area.draw_line(
                 vec![Position::default(), Position::new(0, size.height)],
-                style,
+                self.get_line_style(column == 0, style),
             );
         }
 
-        if self.print_right(column) {
+        if self.print_right(last_column) {
             area.draw_line(
                 vec![
                     Position::new(size.width, 0),
                     Position::new(size.width, size.height),
                 ],
-                style,
+                self.get_line_style(last_column + 1 == self.num_columns, style),
             );
         }
 
-        if self.print_top(row) {
+        if self.print_top(row, is_continued) {
             area.draw_line(
                 vec![Position::default(), Position::new(size.width, 0)],
-                style,
+                self.get_line_style(row == 0 && !is_continued, style),
             );
         }
 
-        if self.print_bottom(row, has_more) {
+        if self.print_bottom(last_row, has_more) {
             area.draw_line(
                 vec![
                     Position::new(0, size.height),
                     Position::new(size.width, size.height),
                 ],
-                style,
+                self.get_line_style(last_row + 1 == self.num_rows && !has_more, style),
             );
         }
+    }
+}
 
-        if column + 1 == self.num_columns {
-            self.last_row = Some(row);
+/// A cell decorator that shades the header rows and stripes the other rows of a table.
+///
+/// The header rows are filled with the header color, and the remaining rows alternately with the
+/// colors for even and odd rows (zebra striping).  By default, the first row is treated as a header
+/// row.  Note that the header rows of a [`TableLayout`][] are passed to the cell decorator as the
+/// first rows of the table.  The fill is drawn beneath the cell content, see
+/// [`Area::defer_background`][].
+///
+/// This decorator can be combined with another decorator, for example a [`FrameCellDecorator`][],
+/// that is called for every cell after the cell has been shaded, see [`set_decorator`][].
+///
+/// # Example
+///
+/// ```
+/// use genpdf::{elements, style};
+/// let decorator = elements::ShadingCellDecorator::new()
+///     .with_header_color(style::Color::Rgb(200, 200, 200))
+///     .with_stripe_colors(None, style::Color::Rgb(240, 240, 240))
+///     .with_decorator(elements::FrameCellDecorator::new(false, true, false));
+/// let mut table = elements::TableLayout::new(vec![1, 1]);
+/// table.set_cell_decorator(decorator);
+/// ```
+///
+/// [`TableLayout`]: struct.TableLayout.html
+/// [`Area::defer_background`]: ../render/struct.Area.html#method.defer_background
+/// [`FrameCellDecorator`]: struct.FrameCellDecorator.html
+/// [`set_decorator`]: #method.set_decorator
+pub struct ShadingCellDecorator {
+    header_color: Option<style::Color>,
+    header_rows: usize,
+    even_color: Option<style::Color>,
+    odd_color: Option<style::Color>,
+    decorator: Option<Box<dyn CellDecorator>>,
+}
+
+impl ShadingCellDecorator {
+    /// Creates a new shading cell decorator with one header row and without colors.
+    pub fn new() -> ShadingCellDecorator {
+        ShadingCellDecorator {
+            header_color: None,
+            header_rows: 1,
+            even_color: None,
+            odd_color: None,
+            decorator: None,
+        }
+    }
+
+    /// Sets the fill color of the header rows.
+    pub fn set_header_color(&mut self, color: impl Into<Option<style::Color>>) {
+        self.header_color = color.into();
+    }
+
+    /// Sets the fill color of the header rows and returns the decorator.
+    pub fn with_header_color(mut self, color: impl Into<Option<style::Color>>) -> Self {
+        self.set_header_color(color);
+        self
+    }
+
+    /// Sets the number of header rows.
+    pub fn set_header_rows(&mut self, rows: usize) {
+        self.header_rows = rows;
+    }
+
+    /// Sets the number of header rows and returns the decorator.
+    pub fn with_header_rows(mut self, rows: usize) -> Self {
+        self.set_header_rows(rows);
+        self
+    }
+
+    /// Sets the fill colors of the even and odd rows after the header rows.  The first row after
+    /// the header rows is an even row.
+    pub fn set_stripe_colors(
+        &mut self,
+        even: impl Into<Option<style::Color>>,
+        odd: impl Into<Option<style::Color>>,
+    ) {
+        self.even_color = even.into();
+        self.odd_color = odd.into();
+    }
+
+    /// Sets the fill colors of the even and odd rows after the header rows and returns the
+    /// decorator.
+    pub fn with_stripe_colors(
+        mut self,
+        even: impl Into<Option<style::Color>>,
+        odd: impl Into<Option<style::Color>>,
+    ) -> Self {
+        self.set_stripe_colors(even, odd);
+        self
+    }
+
+    /// Sets a decorator that is called for every cell after it has been shaded.
+    pub fn set_decorator(&mut self, decorator: impl CellDecorator + 'static) {
+        self.decorator = Some(Box::new(decorator));
+    }
+
+    /// Sets a decorator that is called for every cell after it has been shaded and returns this
+    /// decorator.
+    pub fn with_decorator(mut self, decorator: impl CellDecorator + 'static) -> Self {
+        self.set_decorator(decorator);
+        self
+    }
+
+    fn get_color(&self, row: usize) -> Option<style::Color> {
+        if row < self.header_rows {
+            self.header_color
+        } else if (row - self.header_rows) % 2 == 1 {
+            self.odd_color
+        } else {
+            self.even_color
+        }
+    }
+}
+
+impl Default for ShadingCellDecorator {
+    fn default() -> ShadingCellDecorator {
+        ShadingCellDecorator::new()
+    }
+}
+
+impl CellDecorator for ShadingCellDecorator {
+    fn set_table_size(&mut self, num_columns: usize, num_rows: usize) {
+        if let Some(decorator) = &mut self.decorator {
+            decorator.set_table_size(num_columns, num_rows);
+        }
+    }
+
+    fn decorate_cell(
+        &mut self,
+        column: usize,
+        row: usize,
+        span: CellSpan,
+        has_more: bool,
+        area: render::Area<'_>,
+        style: Style,
+    ) {
+        if let Some(color) = self.get_color(row) {
+            let size = area.size();
+            area.defer_background(move |_, area| {
+                let shape_style = style::ShapeStyle::new()
+                    .with_fill_color(color)
+                    .with_stroke_color(None);
+                let shape_style = inherit_shape_style(&shape_style, style);
+                area.draw_rectangle(Position::default(), size, &shape_style);
+                Ok(())
+            });
+        }
+        if let Some(decorator) = &mut self.decorator {
+            decorator.decorate_cell(column, row, span, has_more, area, style);
+        }
+    }
+}
+
+/// The number of columns and rows that are spanned by a table cell.
+///
+/// By default, a cell spans one column and one row.  A span can be created from a number of
+/// columns or from a tuple with the number of columns and rows.
+///
+/// # Example
+///
+/// ```
+/// use genpdf::elements::CellSpan;
+/// assert_eq!(CellSpan::new(2, 1), CellSpan::from(2));
+/// assert_eq!(CellSpan::new(1, 3), CellSpan::from((1, 3)));
+/// ```
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct CellSpan {
+    /// The number of columns spanned by the cell.
+    pub columns: usize,
+    /// The number of rows spanned by the cell.
+    pub rows: usize,
+}
+
+impl CellSpan {
+    /// Creates a new span with the given number of columns and rows.
+    pub fn new(columns: usize, rows: usize) -> CellSpan {
+        CellSpan { columns, rows }
+    }
+}
+
+impl Default for CellSpan {
+    fn default() -> CellSpan {
+        CellSpan::new(1, 1)
+    }
+}
+
+impl From<usize> for CellSpan {
+    fn from(columns: usize) -> CellSpan {
+        CellSpan::new(columns, 1)
+    }
+}
+
+impl From<(usize, usize)> for CellSpan {
+    fn from((columns, rows): (usize, usize)) -> CellSpan {
+        CellSpan::new(columns, rows)
+    }
+}
+
+/// A row of a table layout.
+///
+/// This is a helper struct for populating a [`TableLayout`][].  After you have added all elements
+/// to the row using [`push_element`][] or [`element`][], you can append the row to the table
+/// layout by calling [`push`][].
+///
+/// Cells that span multiple columns or rows can be added with [`push_element_with_span`][] or
+/// [`element_with_span`][].
+///
+/// # Examples
+///
+/// With setters:
+/// ```
+/// use genpdf::elements;
+/// let mut table = elements::TableLayout::new(vec![1, 1]);
+/// let mut row = table.row();
+/// row.push_element(elements::Paragraph::new("Cell 1"));
+/// row.push_element(elements::Paragraph::new("Cell 2"));
+/// row.push().expect("Invalid table row");
+/// ```
+///
+/// Chained:
+/// ```
+/// use genpdf::elements;
+/// let table = elements::TableLayout::new(vec![1, 1])
+///     .row()
+///     .element(elements::Paragraph::new("Cell 1"))
+///     .element(elements::Paragraph::new("Cell 2"))
+///     .push()
+///     .expect("Invalid table row");
+/// ```
+///
+/// With spans:
+/// ```
+/// use genpdf::elements;
+/// let mut table = elements::TableLayout::new(vec![1, 1, 1]);
+/// table
+///     .row()
+///     .element_with_span(elements::Paragraph::new("Two rows"), (1, 2))
+///     .element_with_span(elements::Paragraph::new("Two columns"), 2)
+///     .push()
+///     .expect("Invalid table row");
+/// table
+///     .row()
+///     .element(elements::Paragraph::new("Cell 1"))
+///     .element(elements::Paragraph::new("Cell 2"))
+///     .push()
+///     .expect("Invalid table row");
+/// ```
+///
+/// [`TableLayout`]: struct.TableLayout.html
+/// [`push`]: #method.push
+/// [`push_element`]: #method.push_element
+/// [`element`]: #method.element
+/// [`push_element_with_span`]: #method.push_element_with_span
+/// [`element_with_span`]: #method.element_with_span
+pub struct TableLayoutRow<'a> {
+    table_layout: &'a mut TableLayout,
+    elements: Vec<Box<dyn Element>>,
+    spans: Vec<CellSpan>,
+}
+
+impl<'a> TableLayoutRow<'a> {
+    fn new(table_layout: &'a mut TableLayout) -> TableLayoutRow<'a> {
+        TableLayoutRow {
+            table_layout,
+            elements: Vec::new(),
+            spans: Vec::new(),
         }
     }
+
+    /// Adds the given element to this row.
+    pub fn push_element<E: Element + 'static>(&mut self, element: E) {
+        self.push_element_with_span(element, CellSpan::default());
+    }
+
+    /// Adds the given element to this row and returns the row.
+    #[must_use]
+    pub fn element<E: Element + 'static>(mut self, element: E) -> Self {
+        self.push_element(element);
+        self
+    }
+
+    /// Adds the given element with the given span to this row.
+    ///
+    /// If the cell spans multiple rows, the following rows have less elements.
+    pub fn push_element_with_span<E: Element + 'static>(
+        &mut self,
+        element: E,
+        span: impl Into<CellSpan>,
+    ) {
+        self.elements.push(Box::new(element));
+        self.spans.push(span.into());
+    }
+
+    /// Adds the given element with the given span to this row and returns the row.
+    #[must_use]
+    pub fn element_with_span<E: Element + 'static>(
+        mut self,
+        element: E,
+        span: impl Into<CellSpan>,
+    ) -> Self {
+        self.push_element_with_span(element, span);
+        self
+    }
+
+    /// Tries to append this row to the table.
+    ///
+    /// This method fails if the elements in this row do not match the columns in the table that
+    /// are not covered by cells of previous rows.
+    pub fn push(self) -> Result<(), Error> {
+        self.table_layout
+            .push_row_with_spans(self.elements, self.spans)
+    }
 }
 
-/// A row of a table layout.
+/// A header row of a table layout.
 ///
-/// This is a helper struct for populating a [`TableLayout`][].  After you have added all elements
-/// to the row using [`push_element`][] or [`element`][], you can append the row to the table
-/// layout by calling [`push`][].
+/// This is a helper struct for adding header rows to a [`TableLayout`][].  It works like
+/// [`TableLayoutRow`][], but as header rows are rendered on every page of the table, the elements
+/// of a header row must implement [`Clone`][].
 ///
-/// # Examples
+/// # Example
 ///
-/// With setters:
 /// ```
 /// use genpdf::elements;
 /// let mut table = elements::TableLayout::new(vec![1, 1]);
-/// let mut row = table.row();
-/// row.push_element(elements::Paragraph::new("Cell 1"));
-/// row.push_element(elements::Paragraph::new("Cell 2"));
-/// row.push().expect("Invalid table row");
-/// ```
-///
-/// Chained:
-/// ```
-/// use genpdf::elements;
-/// let table = elements::TableLayout::new(vec![1, 1])
-///     .row()
-///     .element(elements::Paragraph::new("Cell 1"))
-///     .element(elements::Paragraph::new("Cell 2"))
+/// table
+///     .header_row()
+///     .element(elements::Paragraph::new("Header 1"))
+///     .element(elements::Paragraph::new("Header 2"))
 ///     .push()
 ///     .expect("Invalid table row");
 /// ```
 ///
 /// [`TableLayout`]: struct.TableLayout.html
-/// [`push`]: #method.push
-/// [`push_element`]: #method.push_element
-/// [`element`]: #method.element
-pub struct TableLayoutRow<'a> {
+/// [`TableLayoutRow`]: struct.TableLayoutRow.html
+/// [`Clone`]: https://doc.rust-lang.org/std/clone/trait.Clone.html
+pub struct TableLayoutHeaderRow<'a> {
     table_layout: &'a mut TableLayout,
-    elements: Vec<Box<dyn Element>>,
+    elements: Vec<ElementFactory>,
+    spans: Vec<CellSpan>,
 }
 
-impl<'a> TableLayoutRow<'a> {
-    fn new(table_layout: &'a mut TableLayout) -> TableLayoutRow<'a> {
-        TableLayoutRow {
+impl<'a> TableLayoutHeaderRow<'a> {
+    fn new(table_layout: &'a mut TableLayout) -> TableLayoutHeaderRow<'a> {
+        TableLayoutHeaderRow {
             table_layout,
             elements: Vec::new(),
+            spans: Vec::new(),
         }
     }
 
-    /// Adds the given element to this row.
-    pub fn push_element<E: Element + 'static>(&mut self, element: E) {
-        self.elements.push(Box::new(element));
+    /// Adds the given element to this header row.
+    pub fn push_element<E: Element + Clone + 'static>(&mut self, element: E) {
+        self.push_element_with_span(element, CellSpan::default());
     }
 
-    /// Adds the given element to this row and returns the row.
+    /// Adds the given element to this header row and returns the row.
     #[must_use]
-    pub fn element<E: Element + 'static>(mut self, element: E) -> Self {
+    pub fn element<E: Element + Clone + 'static>(mut self, element: E) -> Self {
         self.push_element(element);
         self
     }
 
-    /// Tries to append this row to the table.
+    /// Adds the given element with the given span to this header row.
+    ///
+    /// Cells of header rows can only span other header rows.
+    pub fn push_element_with_span<E: Element + Clone + 'static>(
+        &mut self,
+        element: E,
+        span: impl Into<CellSpan>,
+    ) {
+        self.elements
+            .push(Box::new(move || Box::new(element.clone())));
+        self.spans.push(span.into());
+    }
+
+    /// Adds the given element with the given span to this header row and returns the row.
+    #[must_use]
+    pub fn element_with_span<E: Element + Clone + 'static>(
+        mut self,
+        element: E,
+        span: impl Into<CellSpan>,
+    ) -> Self {
+        self.push_element_with_span(element, span);
+        self
+    }
+
+    /// Tries to append this header row to the table.
     ///
-    /// This method fails if the number of elements in this row does not match the number of
-    /// columns in the table.
+    /// This method fails if the elements in this row do not match the columns in the table that
+    /// are not covered by cells of previous header rows.
     pub fn push(self) -> Result<(), Error> {
-        self.table_layout.push_row(self.elements)
+        self.table_layout.push_header_row(self.elements, self.spans)
+    }
+}
+
+/// Creates a new instance of an element, used for table header rows.
+type ElementFactory = Box<dyn Fn() -> Box<dyn Element>>;
+
+/// A cell of a table layout.
+struct TableCell<T> {
+    element: T,
+    column: usize,
+    span: CellSpan,
+    is_finished: bool,
+}
+
+impl<T> TableCell<T> {
+    fn new(element: T, column: usize, span: CellSpan) -> TableCell<T> {
+        TableCell {
+            element,
+            column,
+            span,
+            is_finished: false,
+        }
+    }
+
+    /// Returns the span of this cell if it is placed in the given row of a table with the given
+    /// number of rows.
+    fn get_span(&self, row: usize, num_rows: usize) -> CellSpan {
+        CellSpan::new(self.span.columns, self.span.rows.min(num_rows - row))
     }
 }
 
@@ -1124,8 +3617,21 @@ impl<'a> TableLayoutRow<'a> {
 /// If you want to print a typical table with borders around the cells, use the
 /// [`FrameCellDecorator`][].
 ///
-/// The column widths are determined by the weights that have been set in the constructor.  The
-/// table always uses the full width of the provided area.
+/// The column widths are determined by the weights that have been set in the constructor, see
+/// [`new`][].  Alternatively, you can use [`with_column_widths`][] to mix fixed, weighted and
+/// automatic column widths, see [`ColumnWidth`][].  Automatic columns are sized to fit the content
+/// of their cells, similar to the automatic table layout in HTML:  They use the maximum content
+/// width of their cells if there is enough space, but are never narrower than the minimum content
+/// width.  Cells that span multiple columns are taken into account if they are wider than the
+/// columns they span.  The table always uses the full width of the provided area.
+///
+/// Cells can span multiple columns and rows, see [`CellSpan`][].  Rows that are connected by cells
+/// spanning multiple rows are rendered together:  If they do not fit on the current page, all of
+/// their cells are continued on the next page.
+///
+/// If the table does not fit on one page, it is continued on the next page.  Header rows that have
+/// been added with [`header_row`][] are rendered at the top of the table on every page.  They are
+/// passed to the cell decorator as the first rows of the table.
 ///
 /// # Examples
 ///
@@ -1151,12 +3657,37 @@ impl<'a> TableLayoutRow<'a> {
 ///     .expect("Invalid table row");
 /// ```
 ///
+/// With automatic and fixed column widths:
+/// ```
+/// use genpdf::{elements, Mm};
+/// use elements::ColumnWidth;
+/// let table = elements::TableLayout::with_column_widths(vec![
+///     ColumnWidth::Fixed(Mm::from(15)),
+///     ColumnWidth::Auto,
+///     ColumnWidth::Weighted(1),
+/// ])
+/// .row()
+/// .element(elements::Paragraph::new("1."))
+/// .element(elements::Paragraph::new("Sized to fit"))
+/// .element(elements::Paragraph::new("Remaining space"))
+/// .push()
+/// .expect("Invalid table row");
+/// ```
+///
+/// [`new`]: #method.new
+/// [`with_column_widths`]: #method.with_column_widths
 /// [`CellDecorator`]: trait.CellDecorator.html
+/// [`CellSpan`]: struct.CellSpan.html
+/// [`ColumnWidth`]: enum.ColumnWidth.html
 /// [`FrameCellDecorator`]: struct.FrameCellDecorator.html
+/// [`header_row`]: #method.header_row
 pub struct TableLayout {
-    column_weights: Vec<usize>,
-    rows: Vec<Vec<Box<dyn Element>>>,
+    column_widths: Vec<ColumnWidth>,
+    header_rows: Vec<Vec<TableCell<ElementFactory>>>,
+    rows: Vec<Vec<TableCell<Box<dyn Element>>>>,
     render_idx: usize,
+    is_header_only: bool,
+    content_widths: Option<Vec<Option<ContentWidth>>>,
     cell_decorator: Option<Box<dyn CellDecorator>>,
 }
 
@@ -1166,10 +3697,27 @@ impl TableLayout {
     /// The column weights are used to determine the relative width of the columns.  The number of
     /// column weights determines the number of columns in the table.
     pub fn new(column_weights: Vec<usize>) -> TableLayout {
+        let column_widths = column_weights
+            .into_iter()
+            .map(ColumnWidth::Weighted)
+            .collect();
+        TableLayout::with_column_widths(column_widths)
+    }
+
+    /// Creates a new table layout with the given column widths.
+    ///
+    /// The number of column widths determines the number of columns in the table.  See
+    /// [`ColumnWidth`][] for the supported column widths.
+    ///
+    /// [`ColumnWidth`]: enum.ColumnWidth.html
+    pub fn with_column_widths(column_widths: Vec<ColumnWidth>) -> TableLayout {
         TableLayout {
-            column_weights,
+            column_widths,
+            header_rows: Vec::new(),
             rows: Vec::new(),
             render_idx: 0,
+            is_header_only: false,
+            content_widths: None,
             cell_decorator: None,
         }
     }
@@ -1186,51 +3734,155 @@ impl TableLayout {
         TableLayoutRow::new(self)
     }
 
+    /// Adds a header row to this table using the [`TableLayoutHeaderRow`][] helper struct.
+    ///
+    /// Header rows are rendered at the top of the table on every page.
+    ///
+    /// [`TableLayoutHeaderRow`]: struct.TableLayoutHeaderRow.html
+    pub fn header_row(&mut self) -> TableLayoutHeaderRow<'_> {
+        TableLayoutHeaderRow::new(self)
+    }
+
     /// Adds a row to this table.
     ///
-    /// The number of elements in the given vector must match the number of columns.  Otherwise, an
-    /// error is returned.
+    /// The number of elements in the given vector must match the number of columns that are not
+    /// covered by cells of the previous rows that span multiple rows.  Otherwise, an error is
+    /// returned.
     pub fn push_row(&mut self, row: Vec<Box<dyn Element>>) -> Result<(), Error> {
-        if row.len() == self.column_weights.len() {
-            self.rows.push(row);
-            Ok(())
-        } else {
-            Err(Error::new(
-                format!(
-                    "Expected {} elements in table row, received {}",
-                    self.column_weights.len(),
-                    row.len()
-                ),
-                ErrorKind::InvalidData,
-            ))
+        let spans = vec![CellSpan::default(); row.len()];
+        self.push_row_with_spans(row, spans)
+    }
+
+    /// Adds a row with the given cell spans to this table.
+    ///
+    /// The cells are placed from left to right in the columns that are not covered by cells of the
+    /// previous rows that span multiple rows.  There must be one span for every element, and the
+    /// spans must cover exactly these columns.  Otherwise, an error is returned.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use genpdf::{elements, Element};
+    /// use genpdf::elements::{CellSpan, Paragraph};
+    ///
+    /// fn cells(n: usize) -> Vec<Box<dyn Element>> {
+    ///     (0..n).map(|_| Box::new(Paragraph::new("Cell")) as Box<dyn Element>).collect()
+    /// }
+    ///
+    /// let mut table = elements::TableLayout::new(vec![1, 1]);
+    /// // The number of spans must match the number of elements.
+    /// assert!(table.push_row_with_spans(cells(2), vec![CellSpan::new(2, 1)]).is_err());
+    /// assert!(table.push_row_with_spans(cells(1), vec![CellSpan::default(); 2]).is_err());
+    /// // The spans must cover all columns.
+    /// assert!(table.push_row_with_spans(cells(1), vec![CellSpan::new(1, 1)]).is_err());
+    /// // The first cell spans two rows, so the next row only has one free column.
+    /// table
+    ///     .push_row_with_spans(cells(2), vec![CellSpan::new(1, 2), CellSpan::default()])
+    ///     .expect("Invalid table row");
+    /// assert!(table.push_row_with_spans(cells(2), vec![CellSpan::default(); 2]).is_err());
+    /// table
+    ///     .push_row_with_spans(cells(1), vec![CellSpan::default()])
+    ///     .expect("Invalid table row");
+    /// ```
+    pub fn push_row_with_spans(
+        &mut self,
+        row: Vec<Box<dyn Element>>,
+        spans: Vec<CellSpan>,
+    ) -> Result<(), Error> {
+        let cells = place_cells(&self.rows, row, spans, self.column_widths.len())?;
+        self.rows.push(cells);
+        Ok(())
+    }
+
+    fn push_header_row(
+        &mut self,
+        row: Vec<ElementFactory>,
+        spans: Vec<CellSpan>,
+    ) -> Result<(), Error> {
+        let cells = place_cells(&self.header_rows, row, spans, self.column_widths.len())?;
+        self.header_rows.push(cells);
+        Ok(())
+    }
+
+    /// Returns the index of the first row after the row group starting with the given row.
+    ///
+    /// A row group is a sequence of rows that are connected by cells spanning multiple rows.
+    fn get_group_end<T>(rows: &[Vec<TableCell<T>>], start: usize) -> usize {
+        let mut end = start + 1;
+        let mut idx = start;
+        while idx < end {
+            for cell in &rows[idx] {
+                end = end.max(idx + cell.get_span(idx, rows.len()).rows);
+            }
+            idx += 1;
         }
+        end
     }
 
-    fn render_row(
+    /// Renders the given header rows and returns their height, or `None` if they do not fit into
+    /// the given area.
+    fn render_header(
         &mut self,
         context: &Context,
         area: render::Area<'_>,
         style: Style,
-    ) -> Result<RenderResult, Error> {
-        let mut result = RenderResult::default();
-
-        let areas = area.split_horizontally(&self.column_weights);
-        let mut row_height = Mm::from(0);
-        for (area, element) in areas.iter().zip(self.rows[self.render_idx].iter_mut()) {
-            let element_result = element.render(context, area.clone(), style)?;
-            result.has_more |= element_result.has_more;
-            row_height = row_height.max(element_result.size.height);
+        widths: &[Mm],
+        rows: &mut [Vec<TableCell<Box<dyn Element>>>],
+    ) -> Result<Option<Mm>, Error> {
+        let decorator = self.cell_decorator.as_mut();
+        let result = render_rows(context, area, style, widths, rows, 0, decorator)?;
+        if result.has_more {
+            Ok(None)
+        } else {
+            Ok(Some(result.size.height))
         }
-        result.size.height = row_height;
+    }
 
-        if let Some(decorator) = &mut self.cell_decorator {
-            for (i, mut area) in areas.into_iter().enumerate() {
-                area.set_height(row_height);
-                decorator.decorate_cell(i, self.render_idx, result.has_more, area, style);
-            }
-        }
+    /// Creates new elements for the header rows.
+    fn create_header_rows(&self) -> Vec<Vec<TableCell<Box<dyn Element>>>> {
+        self.header_rows
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .map(|cell| TableCell::new((cell.element)(), cell.column, cell.span))
+                    .collect()
+            })
+            .collect()
+    }
 
-        Ok(result)
+    /// Measures the content widths of the columns with an automatic width.
+    fn measure_content_widths(&self, context: &Context, style: Style) -> Vec<Option<ContentWidth>> {
+        self.measure_columns(context, style, &self.rows, |width| {
+            *width == ColumnWidth::Auto
+        })
+    }
+
+    /// Measures the content widths of the columns for which the given function returns true,
+    /// using the header rows and the given rows, see `measure_column_widths`.
+    fn measure_columns<F>(
+        &self,
+        context: &Context,
+        style: Style,
+        rows: &[Vec<TableCell<Box<dyn Element>>>],
+        is_measured: F,
+    ) -> Vec<Option<ContentWidth>>
+    where
+        F: Fn(&ColumnWidth) -> bool,
+    {
+        let header_rows = self.create_header_rows();
+        let cells = header_rows.iter().chain(rows).flatten();
+        measure_column_widths(&self.column_widths, cells, is_measured, |element| {
+            element.content_width(context, style)
+        })
+    }
+
+    fn get_widths(&self, context: &Context, style: Style, width: Mm) -> Vec<Mm> {
+        if let Some(content_widths) = &self.content_widths {
+            distribute_widths(&self.column_widths, content_widths, width)
+        } else {
+            let content_widths = self.measure_content_widths(context, style);
+            distribute_widths(&self.column_widths, &content_widths, width)
+        }
     }
 }
 
@@ -1242,23 +3894,724 @@ impl Element for TableLayout {
         style: Style,
     ) -> Result<RenderResult, Error> {
         let mut result = RenderResult::default();
-        if self.column_weights.is_empty() {
+        if self.column_widths.is_empty() {
             return Ok(result);
         }
+        // We have to measure the cells before rendering them for the first time as their content
+        // width changes during the rendering process.
+        if self.content_widths.is_none() {
+            self.content_widths = Some(self.measure_content_widths(context, style));
+        }
+        let header_len = self.header_rows.len();
         if let Some(decorator) = &mut self.cell_decorator {
-            decorator.set_table_size(self.column_weights.len(), self.rows.len());
+            decorator.set_table_size(self.column_widths.len(), header_len + self.rows.len());
         }
-        result.size.width = area.size().width;
+        let widths = self.get_widths(context, style, area.size().width);
+
+        // If we can measure the height of the header rows, we render them after the first row
+        // group so that we don’t draw the header without any rows at the bottom of a page.
+        // Otherwise, we render them first and make sure that we don’t draw an incomplete header.
+        let header_area = area.clone();
+        let mut header = None;
+        if header_len > 0 {
+            let mut rows = self.create_header_rows();
+            let height = if let Some(heights) = measure_rows(context, style, &widths, &rows) {
+                header = Some(rows);
+                heights.iter().fold(Mm(0.0), |acc, h| acc + *h)
+            } else if let Some(height) =
+                self.render_header(context, area.clone(), style, &widths, &mut rows)?
+            {
+                height
+            } else {
+                result.has_more = true;
+                return Ok(result);
+            };
+            if height > area.size().height {
+                result.has_more = true;
+                return Ok(result);
+            }
+            result.size.height += height;
+            area.add_offset(Position::new(0, height));
+        }
+
+        let mut is_first = true;
         while self.render_idx < self.rows.len() {
-            let row_result = self.render_row(context, area.clone(), style)?;
-            result.size.height += row_result.size.height;
-            area.add_offset(Position::new(0, row_result.size.height));
-            if row_result.has_more {
+            let end = TableLayout::get_group_end(&self.rows, self.render_idx);
+            let rows = &mut self.rows[self.render_idx..end];
+            let (group_result, heights, is_empty) =
+                render_cells(context, &area, style, &widths, rows)?;
+            if is_empty && group_result.has_more {
+                if is_first && (header_len == 0 || header.is_some()) {
+                    // Neither the header nor any row has been drawn, so we move the table to the
+                    // next page.
+                    return Ok(RenderResult {
+                        size: Size::default(),
+                        has_more: true,
+                    });
+                } else if is_first {
+                    // Only the header has been drawn.  If this also happened on the last page,
+                    // the row group does not fit below the header on an empty page.
+                    if self.is_header_only {
+                        return Err(Error::new(
+                            "Could not fit a table row below the table header on a new page",
+                            ErrorKind::PageSizeExceeded,
+                        ));
+                    }
+                    self.is_header_only = true;
+                }
                 break;
             }
-            self.render_idx += 1;
+            is_first = false;
+            self.is_header_only = false;
+            if let Some(mut rows) = header.take() {
+                self.render_header(context, header_area.clone(), style, &widths, &mut rows)?;
+            }
+            if let Some(decorator) = &mut self.cell_decorator {
+                decorate_cells(
+                    &area,
+                    style,
+                    &widths,
+                    &self.rows[self.render_idx..end],
+                    &heights,
+                    header_len + self.render_idx,
+                    group_result.has_more,
+                    decorator,
+                );
+            }
+            result.size.height += group_result.size.height;
+            area.add_offset(Position::new(0, group_result.size.height));
+            if group_result.has_more {
+                break;
+            }
+            self.render_idx = end;
+        }
+        // A table without body rows only consists of its header.
+        if let Some(mut rows) = header.take() {
+            self.render_header(context, header_area, style, &widths, &mut rows)?;
         }
+        result.size.width = area.size().width;
         result.has_more = self.render_idx < self.rows.len();
         Ok(result)
     }
+
+    fn content_width(&self, context: &Context, style: Style) -> Option<ContentWidth> {
+        let rows = &self.rows[self.render_idx..];
+        let content_widths = self.measure_columns(context, style, rows, |_| true);
+        let mut result = ContentWidth::default();
+        for (width, content_width) in self.column_widths.iter().zip(content_widths) {
+            let content_width = match width {
+                ColumnWidth::Fixed(width) => ContentWidth::fixed(*width),
+                _ => content_width?,
+            };
+            result = result.stack_horizontal(content_width);
+        }
+        Some(result)
+    }
+
+    fn content_height(&self, context: &Context, style: Style, width: Mm) -> Option<Mm> {
+        let widths = self.get_widths(context, style, width);
+        let header_rows = self.create_header_rows();
+        let rows = &self.rows[self.render_idx..];
+        let mut result = Mm(0.0);
+        for rows in &[header_rows.as_slice(), rows] {
+            for height in measure_rows(context, style, &widths, rows)? {
+                result += height;
+            }
+        }
+        Some(result)
+    }
+}
+
+/// Measures the content widths of the columns with the given widths for which the given function
+/// returns true, using the given function to measure the elements of the given cells.
+///
+/// The content width of a column is determined by the content widths of the cells that only
+/// span this column.  If a cell that spans multiple columns is wider than these columns, the
+/// difference is distributed evenly among the measured columns it spans, unless it also spans
+/// a column that is not measured and that can absorb the difference.  Fixed columns are never
+/// measured.
+fn measure_column_widths<'c, T: 'c, F, G>(
+    column_widths: &[ColumnWidth],
+    cells: impl Iterator<Item = &'c TableCell<T>>,
+    is_measured: F,
+    measure: G,
+) -> Vec<Option<ContentWidth>>
+where
+    F: Fn(&ColumnWidth) -> bool,
+    G: Fn(&T) -> Option<ContentWidth>,
+{
+    let mut result: Vec<_> = column_widths
+        .iter()
+        .map(|width| match width {
+            ColumnWidth::Fixed(_) => None,
+            width if is_measured(width) => Some(ContentWidth::default()),
+            _ => None,
+        })
+        .collect();
+    if result.iter().all(Option::is_none) {
+        return result;
+    }
+
+    let mut spanning_cells = Vec::new();
+    let mut is_known = vec![true; result.len()];
+    for cell in cells {
+        if cell.span.columns > 1 {
+            spanning_cells.push(cell);
+        } else if let Some(column_width) = &mut result[cell.column] {
+            if let Some(content_width) = measure(&cell.element) {
+                *column_width = column_width.stack_vertical(content_width);
+            } else {
+                is_known[cell.column] = false;
+            }
+        }
+    }
+
+    spanning_cells.sort_by_key(|cell| cell.span.columns);
+    for cell in spanning_cells {
+        let columns = cell.column..(cell.column + cell.span.columns).min(result.len());
+        let mut fixed = Mm(0.0);
+        let mut auto = Vec::new();
+        for i in columns {
+            match (column_widths[i], result[i]) {
+                (ColumnWidth::Fixed(width), _) => fixed += width,
+                (_, Some(content_width)) if is_known[i] => auto.push((i, content_width)),
+                _ => {
+                    auto.clear();
+                    break;
+                }
+            }
+        }
+        if auto.is_empty() {
+            continue;
+        }
+        let content_width = match measure(&cell.element) {
+            Some(content_width) => content_width,
+            None => continue,
+        };
+        let n = auto.len() as f64;
+        let min: Mm = auto.iter().map(|(_, w)| w.min).sum();
+        let max: Mm = auto.iter().map(|(_, w)| w.max).sum();
+        let extra_min = (content_width.min - fixed - min).max(Mm(0.0)) / n;
+        let extra_max = (content_width.max - fixed - max).max(Mm(0.0)) / n;
+        for (i, column_width) in auto {
+            let min = column_width.min + extra_min;
+            let max = (column_width.max + extra_max).max(min);
+            result[i] = Some(ContentWidth::new(min, max));
+        }
+    }
+
+    result
+        .into_iter()
+        .zip(is_known)
+        .map(|(content_width, is_known)| content_width.filter(|_| is_known))
+        .collect()
+}
+
+/// Places the given elements with the given spans in a new row after the given rows.
+fn place_cells<T, U>(
+    rows: &[Vec<TableCell<T>>],
+    elements: Vec<U>,
+    spans: Vec<CellSpan>,
+    num_columns: usize,
+) -> Result<Vec<TableCell<U>>, Error> {
+    if elements.len() != spans.len() {
+        return Err(Error::new(
+            format!(
+                "Expected {} cell spans for the table row, received {}",
+                elements.len(),
+                spans.len()
+            ),
+            ErrorKind::InvalidData,
+        ));
+    }
+
+    // Mark the columns that are covered by cells of the previous rows
+    let row_idx = rows.len();
+    let mut is_free = vec![true; num_columns];
+    for (idx, row) in rows.iter().enumerate() {
+        for cell in row.iter().filter(|cell| idx + cell.span.rows > row_idx) {
+            for is_free in &mut is_free[cell.column..cell.column + cell.span.columns] {
+                *is_free = false;
+            }
+        }
+    }
+
+    let expected = is_free.iter().filter(|is_free| **is_free).count();
+    let received: usize = spans.iter().map(|span| span.columns).sum();
+    if expected != received {
+        return Err(Error::new(
+            format!(
+                "Expected table row cells spanning {} columns, received {}",
+                expected, received
+            ),
+            ErrorKind::InvalidData,
+        ));
+    }
+
+    let mut cells = Vec::with_capacity(elements.len());
+    let mut column = 0;
+    for (element, span) in elements.into_iter().zip(spans) {
+        if span.columns == 0 || span.rows == 0 {
+            return Err(Error::new(
+                "Table cells must span at least one column and one row",
+                ErrorKind::InvalidData,
+            ));
+        }
+        while !is_free[column] {
+            column += 1;
+        }
+        if !is_free[column..column + span.columns]
+            .iter()
+            .all(|is_free| *is_free)
+        {
+            return Err(Error::new(
+                format!(
+                    "The table cell in column {} overlaps with a cell of a previous row",
+                    column
+                ),
+                ErrorKind::InvalidData,
+            ));
+        }
+        cells.push(TableCell::new(element, column, span));
+        column += span.columns;
+    }
+    Ok(cells)
+}
+
+/// Returns the horizontal offset and the width of the given cell.
+fn get_cell_bounds<T>(widths: &[Mm], cell: &TableCell<T>) -> (Mm, Mm) {
+    let sum = |widths: &[Mm]| widths.iter().fold(Mm(0.0), |acc, w| acc + *w);
+    let end = (cell.column + cell.span.columns).min(widths.len());
+    (sum(&widths[..cell.column]), sum(&widths[cell.column..end]))
+}
+
+/// Calculates the heights of the given rows from the content heights of their cells, or returns
+/// `None` if the content height of a cell is not known.
+fn measure_rows(
+    context: &Context,
+    style: Style,
+    widths: &[Mm],
+    rows: &[Vec<TableCell<Box<dyn Element>>>],
+) -> Option<Vec<Mm>> {
+    let row_spans = get_row_spans(rows);
+    let result = layout_rows(&row_spans, |_, row, idx| {
+        let cell = &rows[row][idx];
+        let (_, width) = get_cell_bounds(widths, cell);
+        Ok(cell.element.content_height(context, style, width))
+    });
+    result.ok().and_then(|heights| heights)
+}
+
+/// Returns the number of rows spanned by the cells of the given rows.
+fn get_row_spans<T>(rows: &[Vec<TableCell<T>>]) -> Vec<Vec<usize>> {
+    rows.iter()
+        .enumerate()
+        .map(|(idx, row)| {
+            row.iter()
+                .map(|cell| cell.get_span(idx, rows.len()).rows)
+                .collect()
+        })
+        .collect()
+}
+
+/// Calculates the row heights using the given function to determine the height of a cell.
+///
+/// The function receives the vertical offset, the row index and the index of the cell within the
+/// row.  The cells are processed row by row.  Cells that span multiple rows are processed after the
+/// last row they span.  If such a cell is higher than the rows it spans, the height of the last
+/// row is increased.  If the function returns `None` for a cell, `None` is returned.
+fn layout_rows<F>(row_spans: &[Vec<usize>], mut f: F) -> Result<Option<Vec<Mm>>, Error>
+where
+    F: FnMut(Mm, usize, usize) -> Result<Option<Mm>, Error>,
+{
+    let mut heights = vec![Mm(0.0); row_spans.len()];
+    let mut offsets = vec![Mm(0.0); row_spans.len()];
+    for row in 0..row_spans.len() {
+        if row > 0 {
+            offsets[row] = offsets[row - 1] + heights[row - 1];
+        }
+        for (idx, _) in row_spans[row].iter().enumerate().filter(|(_, n)| **n == 1) {
+            if let Some(height) = f(offsets[row], row, idx)? {
+                heights[row] = heights[row].max(height);
+            } else {
+                return Ok(None);
+            }
+        }
+        for start in 0..row {
+            for (idx, n) in row_spans[start].iter().enumerate() {
+                if *n > 1 && start + n == row + 1 {
+                    let height = if let Some(height) = f(offsets[start], start, idx)? {
+                        height
+                    } else {
+                        return Ok(None);
+                    };
+                    let available = offsets[row] + heights[row] - offsets[start];
+                    if height > available {
+                        heights[row] += height - available;
+                    }
+                }
+            }
+        }
+    }
+    Ok(Some(heights))
+}
+
+/// Renders the given rows into the given area and calls the cell decorator for all cells.
+///
+/// If one of the cells has more content, all unfinished cells of these rows are continued on the
+/// next page.  The row indices passed to the cell decorator start with the given offset.
+fn render_rows(
+    context: &Context,
+    area: render::Area<'_>,
+    style: Style,
+    widths: &[Mm],
+    rows: &mut [Vec<TableCell<Box<dyn Element>>>],
+    row_offset: usize,
+    decorator: Option<&mut Box<dyn CellDecorator>>,
+) -> Result<RenderResult, Error> {
+    let (result, heights, _) = render_cells(context, &area, style, widths, rows)?;
+    if let Some(decorator) = decorator {
+        decorate_cells(
+            &area,
+            style,
+            widths,
+            rows,
+            &heights,
+            row_offset,
+            result.has_more,
+            decorator,
+        );
+    }
+    Ok(result)
+}
+
+/// Renders the cells of the given rows into the given area.
+///
+/// If one of the cells has more content, all unfinished cells of these rows are continued on the
+/// next page.  This function returns the render result, the heights of the rows and whether all
+/// cells have been rendered with an empty size.
+fn render_cells(
+    context: &Context,
+    area: &render::Area<'_>,
+    style: Style,
+    widths: &[Mm],
+    rows: &mut [Vec<TableCell<Box<dyn Element>>>],
+) -> Result<(RenderResult, Vec<Mm>, bool), Error> {
+    let mut result = RenderResult::default();
+    let mut is_empty = true;
+    let row_spans = get_row_spans(rows);
+    let heights = layout_rows(&row_spans, |offset, row, idx| {
+        let cell = &mut rows[row][idx];
+        if cell.is_finished {
+            return Ok(Some(Mm(0.0)));
+        }
+        let cell_area = get_cell_area(area, widths, offset, cell);
+        let cell_result = cell.element.render(context, cell_area, style)?;
+        cell.is_finished = !cell_result.has_more;
+        result.has_more |= cell_result.has_more;
+        is_empty &= cell_result.size == Size::default();
+        Ok(Some(cell_result.size.height))
+    })?
+    .unwrap_or_default();
+
+    result.size.width = area.size().width;
+    result.size.height = heights.iter().fold(Mm(0.0), |acc, h| acc + *h);
+    Ok((result, heights, is_empty))
+}
+
+/// Calls the cell decorator for all cells of the given rows that have been rendered into the
+/// given area with the given row heights.
+///
+/// The row indices passed to the cell decorator start with the given offset.
+#[allow(clippy::too_many_arguments)]
+fn decorate_cells(
+    area: &render::Area<'_>,
+    style: Style,
+    widths: &[Mm],
+    rows: &[Vec<TableCell<Box<dyn Element>>>],
+    heights: &[Mm],
+    row_offset: usize,
+    has_more: bool,
+    decorator: &mut Box<dyn CellDecorator>,
+) {
+    let row_spans = get_row_spans(rows);
+    let mut offset = Mm(0.0);
+    for (row, cells) in rows.iter().enumerate() {
+        for (cell, n) in cells.iter().zip(&row_spans[row]) {
+            let mut cell_area = get_cell_area(area, widths, offset, cell);
+            cell_area.set_height(heights[row..row + n].iter().fold(Mm(0.0), |a, h| a + *h));
+            let span = CellSpan::new(cell.span.columns, *n);
+            decorator.decorate_cell(
+                cell.column,
+                row_offset + row,
+                span,
+                has_more,
+                cell_area,
+                style,
+            );
+        }
+        offset += heights[row];
+    }
+}
+
+/// Returns the area of the given cell in a row with the given vertical offset.
+fn get_cell_area<'a, T>(
+    area: &render::Area<'a>,
+    widths: &[Mm],
+    offset: Mm,
+    cell: &TableCell<T>,
+) -> render::Area<'a> {
+    let (x, width) = get_cell_bounds(widths, cell);
+    let mut cell_area = area.clone();
+    cell_area.add_offset(Position::new(x, offset));
+    cell_area.set_width(width);
+    cell_area
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn place(
+        rows: &mut Vec<Vec<TableCell<()>>>,
+        spans: Vec<(usize, usize)>,
+        num_columns: usize,
+    ) -> Result<Vec<usize>, Error> {
+        let elements = vec![(); spans.len()];
+        let spans = spans.into_iter().map(CellSpan::from).collect();
+        let cells = place_cells(rows, elements, spans, num_columns)?;
+        let columns = cells.iter().map(|cell| cell.column).collect();
+        rows.push(cells);
+        Ok(columns)
+    }
+
+    #[test]
+    fn place_cells_skips_covered_columns() {
+        let mut rows = Vec::new();
+        assert_eq!(
+            vec![0, 1],
+            place(&mut rows, vec![(1, 2), (2, 1)], 3).unwrap()
+        );
+        assert_eq!(
+            vec![1, 2],
+            place(&mut rows, vec![(1, 1), (1, 1)], 3).unwrap()
+        );
+        assert_eq!(
+            vec![0, 2],
+            place(&mut rows, vec![(2, 1), (1, 1)], 3).unwrap()
+        );
+    }
+
+    #[test]
+    fn place_cells_rejects_invalid_spans() {
+        let mut rows = Vec::new();
+        // more elements than spans
+        let spans = vec![CellSpan::new(2, 1)];
+        assert!(place_cells(&rows, vec![(), ()], spans, 2).is_err());
+        // more spans than elements
+        let spans = vec![CellSpan::default(); 2];
+        assert!(place_cells(&rows, vec![()], spans, 2).is_err());
+        // too few and too many columns
+        assert!(place(&mut rows, vec![(1, 1)], 2).is_err());
+        assert!(place(&mut rows, vec![(2, 1), (1, 1)], 2).is_err());
+        // empty spans
+        assert!(place(&mut rows, vec![(0, 1), (2, 1)], 2).is_err());
+        assert!(place(&mut rows, vec![(1, 0), (1, 1)], 2).is_err());
+        assert!(rows.is_empty());
+
+        // overlap with a cell spanning multiple rows
+        assert_eq!(
+            vec![0, 1, 2],
+            place(&mut rows, vec![(1, 1), (1, 2), (1, 1)], 3).unwrap()
+        );
+        assert!(place(&mut rows, vec![(2, 1)], 3).is_err());
+        assert_eq!(
+            vec![0, 2],
+            place(&mut rows, vec![(1, 1), (1, 1)], 3).unwrap()
+        );
+    }
+
+    fn cell(column: usize, columns: usize, min: f64, max: f64) -> TableCell<Option<ContentWidth>> {
+        let content_width = ContentWidth::new(min, max);
+        TableCell::new(Some(content_width), column, CellSpan::from(columns))
+    }
+
+    fn measure(
+        column_widths: &[ColumnWidth],
+        cells: &[TableCell<Option<ContentWidth>>],
+    ) -> Vec<Option<ContentWidth>> {
+        measure_column_widths(column_widths, cells.iter(), |_| true, |w| *w)
+    }
+
+    #[test]
+    fn distribute_widths_with_enough_space() {
+        let widths = [
+            ColumnWidth::Fixed(Mm(20.0)),
+            ColumnWidth::Auto,
+            ColumnWidth::Weighted(1),
+            ColumnWidth::Weighted(3),
+        ];
+        let content_widths = [None, Some(ContentWidth::new(10, 30)), None, None];
+        let result = distribute_widths(&widths, &content_widths, Mm(130.0));
+        assert_eq!(vec![Mm(20.0), Mm(30.0), Mm(20.0), Mm(60.0)], result);
+    }
+
+    #[test]
+    fn distribute_widths_shrinks_auto_columns() {
+        let widths = [
+            ColumnWidth::Auto,
+            ColumnWidth::Auto,
+            ColumnWidth::Weighted(1),
+        ];
+        let content_widths = [
+            Some(ContentWidth::new(10, 50)),
+            Some(ContentWidth::new(20, 40)),
+            None,
+        ];
+        // The auto columns share the space proportionally to their flexibility and the weighted
+        // column is empty.
+        let result = distribute_widths(&widths, &content_widths, Mm(45.0));
+        assert_eq!(vec![Mm(20.0), Mm(25.0), Mm(0.0)], result);
+        // The auto columns are never narrower than their minimum width.
+        let result = distribute_widths(&widths, &content_widths, Mm(20.0));
+        assert_eq!(vec![Mm(10.0), Mm(20.0), Mm(0.0)], result);
+        // With enough space, the weighted column gets the rest.
+        let result = distribute_widths(&widths, &content_widths, Mm(100.0));
+        assert_eq!(vec![Mm(50.0), Mm(40.0), Mm(10.0)], result);
+    }
+
+    #[test]
+    fn distribute_widths_treats_unknown_auto_columns_as_weighted() {
+        let widths = [ColumnWidth::Auto, ColumnWidth::Weighted(3)];
+        let result = distribute_widths(&widths, &[None, None], Mm(40.0));
+        assert_eq!(vec![Mm(10.0), Mm(30.0)], result);
+    }
+
+    #[test]
+    fn measure_column_widths_uses_widest_cell() {
+        let widths = [
+            ColumnWidth::Auto,
+            ColumnWidth::Auto,
+            ColumnWidth::Fixed(Mm(10.0)),
+        ];
+        let cells = [
+            cell(0, 1, 5.0, 10.0),
+            cell(1, 1, 5.0, 20.0),
+            cell(0, 1, 8.0, 8.0),
+        ];
+        let expected = vec![
+            Some(ContentWidth::new(8, 10)),
+            Some(ContentWidth::new(5, 20)),
+            None,
+        ];
+        assert_eq!(expected, measure(&widths, &cells));
+    }
+
+    #[test]
+    fn measure_column_widths_widens_spanned_columns() {
+        let widths = [
+            ColumnWidth::Auto,
+            ColumnWidth::Auto,
+            ColumnWidth::Fixed(Mm(10.0)),
+        ];
+        // A cell spanning the two auto columns that is wider than these columns
+        let cells = [
+            cell(0, 1, 5.0, 10.0),
+            cell(1, 1, 5.0, 20.0),
+            cell(0, 2, 30.0, 50.0),
+        ];
+        let expected = vec![
+            Some(ContentWidth::new(15, 20)),
+            Some(ContentWidth::new(15, 30)),
+            None,
+        ];
+        assert_eq!(expected, measure(&widths, &cells));
+
+        // A cell spanning an auto and a fixed column only widens the auto column.
+        let cells = [cell(1, 1, 5.0, 20.0), cell(1, 2, 40.0, 40.0)];
+        let expected = vec![
+            Some(ContentWidth::default()),
+            Some(ContentWidth::new(30, 30)),
+            None,
+        ];
+        assert_eq!(expected, measure(&widths, &cells));
+
+        // A cell that is narrower than the spanned columns does not change them.
+        let cells = [
+            cell(0, 1, 5.0, 10.0),
+            cell(1, 1, 5.0, 20.0),
+            cell(0, 2, 8.0, 25.0),
+        ];
+        let expected = vec![
+            Some(ContentWidth::new(5, 10)),
+            Some(ContentWidth::new(5, 20)),
+            None,
+        ];
+        assert_eq!(expected, measure(&widths, &cells));
+    }
+
+    #[test]
+    fn measure_column_widths_lets_unmeasured_columns_absorb_spans() {
+        let widths = [ColumnWidth::Auto, ColumnWidth::Weighted(1)];
+        let cells = [cell(0, 1, 5.0, 10.0), cell(0, 2, 30.0, 50.0)];
+        let result = measure_column_widths(
+            &widths,
+            cells.iter(),
+            |width| *width == ColumnWidth::Auto,
+            |w| *w,
+        );
+        assert_eq!(vec![Some(ContentWidth::new(5, 10)), None], result);
+    }
+
+    #[test]
+    fn measure_column_widths_with_unknown_cells() {
+        let widths = [ColumnWidth::Auto, ColumnWidth::Auto];
+        let mut cells = vec![cell(0, 1, 5.0, 10.0), cell(1, 1, 5.0, 10.0)];
+        cells.push(TableCell::new(None, 1, CellSpan::default()));
+        let expected = vec![Some(ContentWidth::new(5, 10)), None];
+        assert_eq!(expected, measure(&widths, &cells));
+    }
+
+    fn layout(row_spans: &[Vec<usize>], heights: &[Vec<f64>]) -> Option<Vec<Mm>> {
+        layout_rows(row_spans, |_, row, idx| Ok(Some(Mm(heights[row][idx])))).unwrap()
+    }
+
+    #[test]
+    fn layout_rows_uses_highest_cell() {
+        let heights = layout(&[vec![1, 1], vec![1, 1]], &[vec![5.0, 8.0], vec![3.0, 2.0]]);
+        assert_eq!(Some(vec![Mm(8.0), Mm(3.0)]), heights);
+    }
+
+    #[test]
+    fn layout_rows_extends_last_spanned_row() {
+        // The first cell spans three rows and is higher than these rows.
+        let row_spans = [vec![3, 1], vec![1], vec![1]];
+        let heights = layout(&row_spans, &[vec![30.0, 5.0], vec![5.0], vec![5.0]]);
+        assert_eq!(Some(vec![Mm(5.0), Mm(5.0), Mm(20.0)]), heights);
+
+        // If the rows are high enough, they are not changed.
+        let heights = layout(&row_spans, &[vec![12.0, 5.0], vec![5.0], vec![5.0]]);
+        assert_eq!(Some(vec![Mm(5.0), Mm(5.0), Mm(5.0)]), heights);
+    }
+
+    #[test]
+    fn layout_rows_passes_offsets() {
+        let mut calls = Vec::new();
+        let row_spans = [vec![2, 1], vec![1]];
+        layout_rows(&row_spans, |offset, row, idx| {
+            calls.push((offset, row, idx));
+            Ok(Some(Mm(4.0)))
+        })
+        .unwrap();
+        let expected = vec![(Mm(0.0), 0, 1), (Mm(4.0), 1, 0), (Mm(0.0), 0, 0)];
+        assert_eq!(expected, calls);
+    }
+
+    #[test]
+    fn layout_rows_requires_all_heights() {
+        let heights = layout_rows(&[vec![1, 1]], |_, _, idx| {
+            Ok(if idx == 0 { Some(Mm(1.0)) } else { None })
+        });
+        assert_eq!(None, heights.unwrap());
+    }
 }
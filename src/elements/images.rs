@@ -0,0 +1,428 @@
+// SPDX-FileCopyrightText: 2020 Robin Krahl <robin.krahl@ireas.org>
+// SPDX-License-Identifier: Apache-2.0 or MIT
+
+//! Image support for `genpdf`.
+//!
+//! *Only available if the `images` feature is enabled.*
+
+use std::fs;
+use std::io;
+use std::path;
+
+use image::GenericImageView as _;
+
+use crate::error::{Context as _, Error};
+use crate::render;
+use crate::style::Style;
+use crate::{ContentWidth, Context, Element, Mm, Position, RenderResult, Scale, Size};
+
+use super::Alignment;
+
+/// The resolution that is used if an image does not specify its resolution.
+const DEFAULT_DPI: f64 = 300.0;
+
+/// An image that is embedded into the document.
+///
+/// *Only available if the `images` feature is enabled.*
+///
+/// This element can be used to render a PNG, JPEG or BMP image.  The size of the image is
+/// calculated from its pixel dimensions and its resolution.  If the resolution is not set with
+/// [`set_dpi`][] or [`with_dpi`][], it is read from the image metadata, falling back to 300 DPI if
+/// the image does not specify it.  The resulting size is multiplied with the [`Scale`][] of the
+/// image.
+///
+/// If the image is narrower than the provided area, it is positioned according to its
+/// [`Alignment`][].  If it is wider than the provided area, it is scaled down to the width of the
+/// area, keeping its aspect ratio.  If it is higher than the remaining space on the page, it is
+/// moved to the next page.
+///
+/// Images with an alpha channel are composited onto a white background as `printpdf` does not
+/// support transparent images.
+///
+/// # Examples
+///
+/// With setters:
+/// ```no_run
+/// use genpdf::elements;
+/// let mut image = elements::Image::from_path("logo.png").expect("Failed to load image");
+/// image.set_alignment(elements::Alignment::Center);
+/// image.set_scale(0.5);
+/// ```
+///
+/// Chained:
+/// ```no_run
+/// use genpdf::elements;
+/// let image = elements::Image::from_path("logo.png")
+///     .expect("Failed to load image")
+///     .aligned(elements::Alignment::Right)
+///     .with_dpi(150.0);
+/// ```
+///
+/// [`set_dpi`]: #method.set_dpi
+/// [`with_dpi`]: #method.with_dpi
+/// [`Scale`]: ../struct.Scale.html
+/// [`Alignment`]: enum.Alignment.html
+#[derive(Clone, Debug)]
+pub struct Image {
+    data: image::DynamicImage,
+    alignment: Alignment,
+    scale: Scale,
+    dpi: Option<f64>,
+}
+
+impl Image {
+    /// Creates a new image from the given image data.
+    ///
+    /// As a [`DynamicImage`][] does not store the image resolution, the default resolution of
+    /// 300 DPI is used unless it is changed with [`set_dpi`][].
+    ///
+    /// [`DynamicImage`]: https://docs.rs/image/0.23/image/enum.DynamicImage.html
+    /// [`set_dpi`]: #method.set_dpi
+    pub fn from_dynamic_image(data: image::DynamicImage) -> Image {
+        Image {
+            data: normalize(data),
+            alignment: Alignment::default(),
+            scale: Scale::default(),
+            dpi: None,
+        }
+    }
+
+    /// Reads an image from the given reader.
+    ///
+    /// The image format is detected automatically.  Supported formats are PNG, JPEG and BMP.
+    pub fn from_reader(mut reader: impl io::Read) -> Result<Image, Error> {
+        let mut buf = Vec::new();
+        reader
+            .read_to_end(&mut buf)
+            .context("Failed to read image data")?;
+        let data = image::load_from_memory(&buf).context("Failed to decode image")?;
+        let mut image = Image::from_dynamic_image(data);
+        image.dpi = read_dpi(&buf);
+        Ok(image)
+    }
+
+    /// Reads an image from the file at the given path.
+    ///
+    /// The image format is detected automatically.  Supported formats are PNG, JPEG and BMP.
+    pub fn from_path(path: impl AsRef<path::Path>) -> Result<Image, Error> {
+        let path = path.as_ref();
+        let file = fs::File::open(path)
+            .with_context(|| format!("Could not open image file {}", path.display()))?;
+        Image::from_reader(io::BufReader::new(file))
+    }
+
+    /// Sets the alignment of this image within the provided area.
+    pub fn set_alignment(&mut self, alignment: Alignment) {
+        self.alignment = alignment;
+    }
+
+    /// Sets the alignment of this image within the provided area and returns the image.
+    pub fn aligned(mut self, alignment: Alignment) -> Self {
+        self.set_alignment(alignment);
+        self
+    }
+
+    /// Sets the scaling factor for this image.
+    pub fn set_scale(&mut self, scale: impl Into<Scale>) {
+        self.scale = scale.into();
+    }
+
+    /// Sets the scaling factor for this image and returns the image.
+    pub fn with_scale(mut self, scale: impl Into<Scale>) -> Self {
+        self.set_scale(scale);
+        self
+    }
+
+    /// Sets the resolution of this image in dots per inch, overriding the resolution stored in the
+    /// image metadata.
+    pub fn set_dpi(&mut self, dpi: f64) {
+        self.dpi = Some(dpi);
+    }
+
+    /// Sets the resolution of this image in dots per inch and returns the image.
+    pub fn with_dpi(mut self, dpi: f64) -> Self {
+        self.set_dpi(dpi);
+        self
+    }
+
+    /// Returns the size of this image on the page if it is not scaled down to fit the provided
+    /// area.
+    pub fn size(&self) -> Size {
+        let mm_per_px = 25.4 / self.dpi.unwrap_or(DEFAULT_DPI);
+        let (width, height) = self.data.dimensions();
+        Size::new(
+            Mm::from(f64::from(width) * mm_per_px * self.scale.x),
+            Mm::from(f64::from(height) * mm_per_px * self.scale.y),
+        )
+    }
+
+    /// Returns the size and the scale of this image if it is rendered in an area with the given
+    /// width.
+    fn fit_to_width(&self, max_width: Mm) -> (Size, Scale) {
+        let size = self.size();
+        if size.width > max_width {
+            let factor = f64::from(max_width) / f64::from(size.width);
+            let scale = Scale::new(self.scale.x * factor, self.scale.y * factor);
+            (Size::new(max_width, size.height * factor), scale)
+        } else {
+            (size, self.scale)
+        }
+    }
+
+    fn get_offset(&self, width: Mm, max_width: Mm) -> Mm {
+        match self.alignment {
+            Alignment::Left | Alignment::Justified => Mm::default(),
+            Alignment::Center => (max_width - width) / 2.0,
+            Alignment::Right => max_width - width,
+        }
+    }
+}
+
+impl Element for Image {
+    fn render(
+        &mut self,
+        _context: &Context,
+        area: render::Area<'_>,
+        _style: Style,
+    ) -> Result<RenderResult, Error> {
+        let mut result = RenderResult::default();
+        let (size, scale) = self.fit_to_width(area.size().width);
+        if size.height > area.size().height {
+            result.has_more = true;
+            return Ok(result);
+        }
+        let offset = self.get_offset(size.width, area.size().width);
+        area.add_image(
+            &self.data,
+            Position::new(offset, 0),
+            scale,
+            self.dpi.unwrap_or(DEFAULT_DPI),
+        );
+        result.size = size;
+        Ok(result)
+    }
+
+    fn content_width(&self, _context: &Context, _style: Style) -> Option<ContentWidth> {
+        Some(ContentWidth::fixed(self.size().width))
+    }
+
+    fn content_height(&self, _context: &Context, _style: Style, width: Mm) -> Option<Mm> {
+        Some(self.fit_to_width(width).0.height)
+    }
+}
+
+/// Converts the given image into a color type that is supported by `printpdf`.
+fn normalize(data: image::DynamicImage) -> image::DynamicImage {
+    use image::DynamicImage;
+
+    match data {
+        DynamicImage::ImageLuma8(_)
+        | DynamicImage::ImageLuma16(_)
+        | DynamicImage::ImageRgb8(_)
+        | DynamicImage::ImageRgb16(_) => data,
+        data if data.color().has_alpha() => {
+            let mut rgba = data.to_rgba8();
+            for pixel in rgba.pixels_mut() {
+                let alpha = u16::from(pixel[3]);
+                for channel in &mut pixel.0[..3] {
+                    let value = u16::from(*channel) * alpha + 255 * (255 - alpha);
+                    *channel = (value / 255) as u8;
+                }
+            }
+            DynamicImage::ImageRgb8(DynamicImage::ImageRgba8(rgba).to_rgb8())
+        }
+        data => DynamicImage::ImageRgb8(data.to_rgb8()),
+    }
+}
+
+/// Tries to read the horizontal resolution in dots per inch from the metadata of the given PNG,
+/// JPEG or BMP image.
+fn read_dpi(data: &[u8]) -> Option<f64> {
+    let dpi = if data.starts_with(b"\x89PNG\r\n\x1a\n") {
+        read_png_dpi(data)
+    } else if data.starts_with(b"\xff\xd8") {
+        read_jpeg_dpi(data)
+    } else if data.starts_with(b"BM") {
+        read_bmp_dpi(data)
+    } else {
+        None
+    };
+    dpi.filter(|dpi| *dpi > 0.0)
+}
+
+fn read_png_dpi(data: &[u8]) -> Option<f64> {
+    // Chunks consist of the data length (4 bytes), the chunk type (4 bytes), the data and a CRC
+    // (4 bytes).  The resolution is stored in the pHYs chunk that must precede the image data.
+    let mut data = data.get(8..)?;
+    while data.len() >= 8 {
+        let len = read_u32_be(data, 0)? as usize;
+        let chunk = data.get(8..8 + len)?;
+        match &data[4..8] {
+            b"pHYs" if len >= 9 => {
+                // Unit 1 means pixels per meter, unit 0 only specifies the aspect ratio.
+                if chunk[8] == 1 {
+                    return Some(f64::from(read_u32_be(chunk, 0)?) * 0.0254);
+                } else {
+                    return None;
+                }
+            }
+            b"IDAT" | b"IEND" => return None,
+            _ => {}
+        }
+        data = data.get(12 + len..)?;
+    }
+    None
+}
+
+fn read_jpeg_dpi(data: &[u8]) -> Option<f64> {
+    // Segments consist of a marker (0xff and the marker type) and the data length (2 bytes,
+    // including the length field).  The resolution is stored in the JFIF APP0 segment.
+    let mut data = data.get(2..)?;
+    while data.len() >= 4 && data[0] == 0xff {
+        let marker = data[1];
+        let len = usize::from(read_u16_be(data, 2)?);
+        let segment = data.get(4..2 + len)?;
+        if marker == 0xe0 && segment.starts_with(b"JFIF\0") && segment.len() >= 12 {
+            let density = f64::from(read_u16_be(segment, 8)?);
+            // Unit 1 means dots per inch, unit 2 dots per centimeter and unit 0 only specifies the
+            // aspect ratio.
+            return match segment[7] {
+                1 => Some(density),
+                2 => Some(density * 2.54),
+                _ => None,
+            };
+        } else if marker == 0xda {
+            // Start of scan, no more metadata
+            return None;
+        }
+        data = data.get(2 + len..)?;
+    }
+    None
+}
+
+fn read_bmp_dpi(data: &[u8]) -> Option<f64> {
+    // The BITMAPINFOHEADER stores the horizontal resolution in pixels per meter at offset 38.
+    let bytes = data.get(38..42)?;
+    let ppm = i32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+    Some(f64::from(ppm) * 0.0254)
+}
+
+fn read_u16_be(data: &[u8], offset: usize) -> Option<u16> {
+    let bytes = data.get(offset..offset + 2)?;
+    Some(u16::from_be_bytes([bytes[0], bytes[1]]))
+}
+
+fn read_u32_be(data: &[u8], offset: usize) -> Option<u32> {
+    let bytes = data.get(offset..offset + 4)?;
+    Some(u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn png(chunks: &[(&[u8], Vec<u8>)]) -> Vec<u8> {
+        let mut data = b"\x89PNG\r\n\x1a\n".to_vec();
+        for (chunk_type, chunk) in chunks {
+            data.extend_from_slice(&(chunk.len() as u32).to_be_bytes());
+            data.extend_from_slice(chunk_type);
+            data.extend_from_slice(chunk);
+            // The CRC is not checked.
+            data.extend_from_slice(&[0; 4]);
+        }
+        data
+    }
+
+    fn phys(ppm: u32, unit: u8) -> Vec<u8> {
+        let mut chunk = ppm.to_be_bytes().to_vec();
+        chunk.extend_from_slice(&ppm.to_be_bytes());
+        chunk.push(unit);
+        chunk
+    }
+
+    fn jpeg(unit: u8, density: u16) -> Vec<u8> {
+        let mut data = b"\xff\xd8\xff\xe0\x00\x10JFIF\x00\x01\x02".to_vec();
+        data.push(unit);
+        data.extend_from_slice(&density.to_be_bytes());
+        data.extend_from_slice(&density.to_be_bytes());
+        data.extend_from_slice(&[0, 0]);
+        data.extend_from_slice(b"\xff\xda\x00\x02");
+        data
+    }
+
+    fn bmp(ppm: i32) -> Vec<u8> {
+        let mut data = b"BM".to_vec();
+        data.resize(38, 0);
+        data.extend_from_slice(&ppm.to_le_bytes());
+        data.extend_from_slice(&ppm.to_le_bytes());
+        data.resize(54, 0);
+        data
+    }
+
+    fn assert_dpi(expected: Option<f64>, data: &[u8]) {
+        let dpi = read_dpi(data);
+        match (expected, dpi) {
+            (Some(expected), Some(dpi)) => assert!((expected - dpi).abs() < 1e-6, "{}", dpi),
+            _ => assert_eq!(expected, dpi),
+        }
+    }
+
+    #[test]
+    fn png_dpi() {
+        let header = (&b"IHDR"[..], vec![0; 13]);
+        let end = (&b"IEND"[..], Vec::new());
+        let data = png(&[header.clone(), (b"pHYs", phys(5000, 1)), end.clone()]);
+        assert_dpi(Some(127.0), &data);
+        let data = png(&[header.clone(), (b"pHYs", phys(5000, 0)), end.clone()]);
+        assert_dpi(None, &data);
+        let data = png(&[
+            header.clone(),
+            (b"IDAT", vec![0; 4]),
+            (b"pHYs", phys(5000, 1)),
+        ]);
+        assert_dpi(None, &data);
+        assert_dpi(None, &png(&[header, end]));
+    }
+
+    #[test]
+    fn jpeg_dpi() {
+        assert_dpi(None, &jpeg(0, 1));
+        assert_dpi(Some(72.0), &jpeg(1, 72));
+        assert_dpi(Some(254.0), &jpeg(2, 100));
+        assert_dpi(None, &jpeg(1, 0));
+    }
+
+    #[test]
+    fn bmp_dpi() {
+        assert_dpi(Some(127.0), &bmp(5000));
+        assert_dpi(None, &bmp(0));
+        assert_dpi(None, &bmp(-5000));
+    }
+
+    #[test]
+    fn dpi_from_invalid_data() {
+        let header = (&b"IHDR"[..], vec![0; 13]);
+        // The images and the length of the data up to the end of the resolution
+        let images = [
+            (png(&[header, (b"pHYs", phys(5000, 1))]), 50),
+            (jpeg(1, 72), 20),
+            (bmp(5000), 42),
+        ];
+        for (data, end) in &images {
+            for len in 0..data.len() {
+                let dpi = read_dpi(&data[..len]);
+                assert_eq!(len >= *end, dpi.is_some(), "{:?}", &data[..len]);
+            }
+        }
+        assert_dpi(None, b"");
+        assert_dpi(None, b"GIF89a");
+        assert_dpi(
+            None,
+            b"\x89PNG\r\n\x1a\n\xff\xff\xff\xff\xff\xff\xff\xff\xff\xff\xff\xff",
+        );
+        assert_dpi(None, b"\xff\xd8\xff\xe0\x00\x00\xff\xff\xff\xff");
+        assert_dpi(None, b"\xff\xd8\xff\xe0\x00\x01JFIF");
+        assert_dpi(None, b"\xff\xd8\xff\xe0\x00\x08JFIF\x00\x01\x02\x01");
+        assert_dpi(None, b"\xff\xd8\x00\x00\x00\x00");
+    }
+}
@@ -85,6 +85,9 @@ impl error::Error for Error {
             ErrorKind::PdfError(err) => Some(err),
             ErrorKind::PdfIndexError(err) => Some(err),
             ErrorKind::RusttypeError(err) => Some(err),
+            ErrorKind::LopdfError(err) => Some(err),
+            #[cfg(feature = "images")]
+            ErrorKind::ImageError(err) => Some(err),
         }
     }
 }
@@ -111,6 +114,13 @@ pub enum ErrorKind {
     PdfIndexError(printpdf::IndexError),
     /// An error caused by `rusttype`.
     RusttypeError(rusttype::Error),
+    /// An error caused by `lopdf`.
+    LopdfError(lopdf::Error),
+    /// An error caused by `image`.
+    ///
+    /// *Only available if the `images` feature is enabled.*
+    #[cfg(feature = "images")]
+    ImageError(image::ImageError),
 }
 
 impl From<io::Error> for ErrorKind {
@@ -147,3 +157,16 @@ impl From<rusttype::Error> for ErrorKind {
         ErrorKind::RusttypeError(error)
     }
 }
+
+impl From<lopdf::Error> for ErrorKind {
+    fn from(error: lopdf::Error) -> ErrorKind {
+        ErrorKind::LopdfError(error)
+    }
+}
+
+#[cfg(feature = "images")]
+impl From<image::ImageError> for ErrorKind {
+    fn from(error: image::ImageError) -> ErrorKind {
+        ErrorKind::ImageError(error)
+    }
+}
@@ -109,7 +109,7 @@ impl FontCache {
             RawFontData::Builtin(_) => true,
             RawFontData::Embedded(_) => false,
         };
-        let font = Font::new(self.fonts.len(), is_builtin, &font_data.rt_font);
+        let font = Font::new(self.fonts.len(), is_builtin, &font_data);
         self.fonts.push(font_data);
         font
     }
@@ -131,7 +131,7 @@ impl FontCache {
         for font in &self.fonts {
             let pdf_font = match &font.raw_data {
                 RawFontData::Builtin(builtin) => renderer.add_builtin_font(*builtin)?,
-                RawFontData::Embedded(data) => renderer.add_embedded_font(&data)?,
+                RawFontData::Embedded(data) => renderer.add_embedded_font(data)?,
             };
             self.pdf_fonts.push(pdf_font);
         }
@@ -173,6 +173,7 @@ impl FontCache {
 pub struct FontData {
     rt_font: rusttype::Font<'static>,
     raw_data: RawFontData,
+    metrics: FontMetrics,
 }
 
 impl FontData {
@@ -190,6 +191,7 @@ impl FontData {
         } else {
             RawFontData::Embedded(data.clone())
         };
+        let metrics = FontMetrics::read(&data);
         let rt_font = rusttype::Font::from_bytes(data).context("Failed to read rusttype font")?;
         if rt_font.units_per_em() == 0 {
             Err(Error::new(
@@ -197,7 +199,11 @@ impl FontData {
                 ErrorKind::InvalidFont,
             ))
         } else {
-            Ok(FontData { rt_font, raw_data })
+            Ok(FontData {
+                rt_font,
+                raw_data,
+                metrics,
+            })
         }
     }
 
@@ -219,6 +225,74 @@ impl FontData {
     }
 }
 
+/// Additional metrics of a font in font units, as stored in the `post` and `OS/2` tables of
+/// TrueType and OpenType fonts.
+///
+/// For the underline and the strikeout, the values are the position of the center of the line
+/// and the thickness of the line.  (The fonts store the position of the top of the line, so we
+/// subtract half of the thickness.)  For superscripts and subscripts, the values are the vertical
+/// offset from the baseline (positive values are above the baseline for superscripts and below
+/// the baseline for subscripts) and the font size.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+struct FontMetrics {
+    underline: Option<(f32, f32)>,
+    strikeout: Option<(f32, f32)>,
+    superscript: Option<(f32, f32)>,
+    subscript: Option<(f32, f32)>,
+}
+
+impl FontMetrics {
+    fn read(data: &[u8]) -> FontMetrics {
+        let read = |table: Option<&[u8]>, position: usize, size: usize| {
+            let table = table?;
+            let position = read_i16(table, position)?;
+            let size = read_i16(table, size)?;
+            if size > 0 {
+                Some((f32::from(position), f32::from(size)))
+            } else {
+                None
+            }
+        };
+        let to_center = |(top, thickness): (f32, f32)| (top - thickness / 2.0, thickness);
+        let os2 = find_table(data, b"OS/2");
+        FontMetrics {
+            underline: read(find_table(data, b"post"), 8, 10).map(to_center),
+            strikeout: read(os2, 28, 26).map(to_center),
+            superscript: read(os2, 24, 20),
+            subscript: read(os2, 16, 12),
+        }
+    }
+}
+
+/// Returns the table with the given tag from the given TrueType or OpenType font data.  For font
+/// collections, the first font is used.
+fn find_table<'d>(data: &'d [u8], tag: &[u8]) -> Option<&'d [u8]> {
+    let read_u32 = |offset: usize| {
+        data.get(offset..offset + 4)
+            .map(|b| u32::from_be_bytes([b[0], b[1], b[2], b[3]]) as usize)
+    };
+    let start = if data.get(..4) == Some(b"ttcf") {
+        read_u32(12)?
+    } else {
+        0
+    };
+    let num_tables = read_i16(data, start + 4)? as u16 as usize;
+    for i in 0..num_tables {
+        let record = start + 12 + 16 * i;
+        if data.get(record..record + 4)? == tag {
+            let offset = read_u32(record + 8)?;
+            let length = read_u32(record + 12)?;
+            return data.get(offset..offset + length);
+        }
+    }
+    None
+}
+
+fn read_i16(data: &[u8], offset: usize) -> Option<i16> {
+    data.get(offset..offset + 2)
+        .map(|b| i16::from_be_bytes([b[0], b[1]]))
+}
+
 #[derive(Clone, Debug)]
 enum RawFontData {
     Builtin(printpdf::BuiltinFont),
@@ -333,10 +407,18 @@ pub struct Font {
     scale: rusttype::Scale,
     line_height: Mm,
     glyph_height: Mm,
+    ascent: Mm,
+    underline: DecorationMetrics,
+    strikethrough: DecorationMetrics,
+    overline: DecorationMetrics,
+    // the font size factor and the offset of superscripts and subscripts
+    superscript: (f64, Mm),
+    subscript: (f64, Mm),
 }
 
 impl Font {
-    fn new(idx: usize, is_builtin: bool, rt_font: &rusttype::Font<'static>) -> Font {
+    fn new(idx: usize, is_builtin: bool, font_data: &FontData) -> Font {
+        let rt_font = &font_data.rt_font;
         let units_per_em = rt_font.units_per_em();
         assert!(units_per_em != 0);
 
@@ -347,12 +429,39 @@ impl Font {
 
         let line_height = glyph_height + v_metrics.line_gap / units_per_em;
 
+        // If the font does not provide the additional metrics, we use typical values.
+        let to_mm = |value: f32| -> Mm { printpdf::Pt(f64::from(value / units_per_em)).into() };
+        let (underline_position, thickness) = font_data
+            .metrics
+            .underline
+            .unwrap_or((-0.1 * units_per_em, 0.05 * units_per_em));
+        let underline = DecorationMetrics::new(to_mm(underline_position), to_mm(thickness));
+        let strikethrough = if let Some((position, thickness)) = font_data.metrics.strikeout {
+            DecorationMetrics::new(to_mm(position), to_mm(thickness))
+        } else {
+            DecorationMetrics::new(to_mm(0.25 * units_per_em), to_mm(thickness))
+        };
+        let overline =
+            DecorationMetrics::new(to_mm(v_metrics.ascent - thickness / 2.0), to_mm(thickness));
+        let script = |metrics: Option<(f32, f32)>, offset: f32, sign: f32| {
+            let (offset, size) = metrics.unwrap_or((offset * units_per_em, 0.6 * units_per_em));
+            (f64::from(size / units_per_em), to_mm(sign * offset))
+        };
+        let superscript = script(font_data.metrics.superscript, 0.35, 1.0);
+        let subscript = script(font_data.metrics.subscript, 0.15, -1.0);
+
         Font {
             idx,
             is_builtin,
             scale,
             line_height: printpdf::Pt(f64::from(line_height)).into(),
             glyph_height: printpdf::Pt(f64::from(glyph_height)).into(),
+            ascent: to_mm(v_metrics.ascent),
+            underline,
+            strikethrough,
+            overline,
+            superscript,
+            subscript,
         }
     }
 
@@ -371,6 +480,52 @@ impl Font {
         self.glyph_height * f64::from(font_size)
     }
 
+    /// Returns the ascent, i. e. the height above the baseline, for text with this font and the
+    /// given font size.
+    pub fn ascent(&self, font_size: u8) -> Mm {
+        self.ascent * f64::from(font_size)
+    }
+
+    /// Returns the descent, i. e. the depth below the baseline, for text with this font and the
+    /// given font size.
+    pub fn descent(&self, font_size: u8) -> Mm {
+        (self.glyph_height - self.ascent) * f64::from(font_size)
+    }
+
+    /// Returns the metrics of superscript text with this font and the given font size.
+    pub fn superscript_metrics(&self, font_size: u8) -> ScriptMetrics {
+        let (factor, offset) = self.superscript;
+        ScriptMetrics::new(
+            scale_font_size(font_size, factor),
+            offset * f64::from(font_size),
+        )
+    }
+
+    /// Returns the metrics of subscript text with this font and the given font size.
+    pub fn subscript_metrics(&self, font_size: u8) -> ScriptMetrics {
+        let (factor, offset) = self.subscript;
+        ScriptMetrics::new(
+            scale_font_size(font_size, factor),
+            offset * f64::from(font_size),
+        )
+    }
+
+    /// Returns the metrics of an underline for text with this font and the given font size.
+    pub fn underline_metrics(&self, font_size: u8) -> DecorationMetrics {
+        self.underline * f64::from(font_size)
+    }
+
+    /// Returns the metrics of a strikethrough line for text with this font and the given font
+    /// size.
+    pub fn strikethrough_metrics(&self, font_size: u8) -> DecorationMetrics {
+        self.strikethrough * f64::from(font_size)
+    }
+
+    /// Returns the metrics of an overline for text with this font and the given font size.
+    pub fn overline_metrics(&self, font_size: u8) -> DecorationMetrics {
+        self.overline * f64::from(font_size)
+    }
+
     /// Returns the width of a character with this font and the given font size.
     ///
     /// The given [`FontCache`][] must be the font cache that loaded this font.
@@ -400,6 +555,81 @@ impl Font {
     }
 }
 
+/// The position and thickness of a line that decorates text, for example an underline.
+///
+/// These metrics are provided by the [`Font`][] methods [`underline_metrics`][],
+/// [`strikethrough_metrics`][] and [`overline_metrics`][].
+///
+/// [`Font`]: struct.Font.html
+/// [`underline_metrics`]: struct.Font.html#method.underline_metrics
+/// [`strikethrough_metrics`]: struct.Font.html#method.strikethrough_metrics
+/// [`overline_metrics`]: struct.Font.html#method.overline_metrics
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct DecorationMetrics {
+    /// The vertical offset of the center of the line from the baseline.  Positive values are
+    /// above the baseline, negative values are below the baseline.
+    pub position: Mm,
+    /// The thickness of the line.
+    pub thickness: Mm,
+}
+
+impl DecorationMetrics {
+    /// Creates new decoration metrics with the given position and thickness.
+    pub fn new(position: impl Into<Mm>, thickness: impl Into<Mm>) -> DecorationMetrics {
+        DecorationMetrics {
+            position: position.into(),
+            thickness: thickness.into(),
+        }
+    }
+}
+
+impl std::ops::Mul<f64> for DecorationMetrics {
+    type Output = DecorationMetrics;
+
+    fn mul(self, factor: f64) -> DecorationMetrics {
+        DecorationMetrics::new(self.position * factor, self.thickness * factor)
+    }
+}
+
+/// The font size and the baseline offset of superscript or subscript text.
+///
+/// These metrics are provided by the [`Font`][] methods [`superscript_metrics`][] and
+/// [`subscript_metrics`][].
+///
+/// [`Font`]: struct.Font.html
+/// [`superscript_metrics`]: struct.Font.html#method.superscript_metrics
+/// [`subscript_metrics`]: struct.Font.html#method.subscript_metrics
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct ScriptMetrics {
+    /// The font size of the text in points.
+    pub font_size: u8,
+    /// The vertical offset of the baseline of the text from the regular baseline.  Positive values
+    /// are above the regular baseline, negative values are below the regular baseline.
+    pub offset: Mm,
+}
+
+impl ScriptMetrics {
+    /// Creates new script metrics with the given font size and offset.
+    pub fn new(font_size: u8, offset: impl Into<Mm>) -> ScriptMetrics {
+        ScriptMetrics {
+            font_size,
+            offset: offset.into(),
+        }
+    }
+}
+
+/// Scales the given font size with the given factor and rounds it to the nearest positive integer.
+fn scale_font_size(font_size: u8, factor: f64) -> u8 {
+    let font_size = (f64::from(font_size) * factor).round();
+    if font_size < 1.0 {
+        1
+    } else if font_size > 255.0 {
+        255
+    } else {
+        font_size as u8
+    }
+}
+
 fn from_file(
     dir: impl AsRef<path::Path>,
     name: &str,
@@ -408,7 +638,7 @@ fn from_file(
 ) -> Result<FontData, Error> {
     let builtin = builtin.map(|b| b.style(style));
     FontData::load(
-        &dir.as_ref().join(format!("{}-{}.ttf", name, style)),
+        dir.as_ref().join(format!("{}-{}.ttf", name, style)),
         builtin,
     )
 }
@@ -437,3 +667,94 @@ pub fn from_files(
         bold_italic: from_file(dir, name, FontStyle::BoldItalic, builtin)?,
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // The relevant values of the post and OS/2 tables of DejaVu Sans
+    const UNDERLINE: (i16, i16) = (-40, 90);
+    const STRIKEOUT: (i16, i16) = (530, 102);
+    const SUPERSCRIPT: (i16, i16) = (983, 1433);
+    const SUBSCRIPT: (i16, i16) = (286, 1433);
+
+    fn put_i16(table: &mut [u8], offset: usize, value: i16) {
+        table[offset..offset + 2].copy_from_slice(&value.to_be_bytes());
+    }
+
+    /// Creates the data of a font with the given header and tables.
+    fn font_data(header: &[u8], tables: &[(&[u8], Vec<u8>)]) -> Vec<u8> {
+        let mut data = header.to_vec();
+        let start = data.len();
+        data.extend_from_slice(b"\0\x01\0\0");
+        data.extend_from_slice(&(tables.len() as u16).to_be_bytes());
+        data.extend_from_slice(&[0; 6]);
+        let mut offset = start + 12 + 16 * tables.len();
+        for (tag, table) in tables {
+            data.extend_from_slice(tag);
+            data.extend_from_slice(&[0; 4]);
+            data.extend_from_slice(&(offset as u32).to_be_bytes());
+            data.extend_from_slice(&(table.len() as u32).to_be_bytes());
+            offset += table.len();
+        }
+        for (_, table) in tables {
+            data.extend_from_slice(table);
+        }
+        data
+    }
+
+    fn tables() -> Vec<(&'static [u8], Vec<u8>)> {
+        let mut post = vec![0; 32];
+        put_i16(&mut post, 8, UNDERLINE.0);
+        put_i16(&mut post, 10, UNDERLINE.1);
+        let mut os2 = vec![0; 96];
+        put_i16(&mut os2, 12, SUBSCRIPT.1);
+        put_i16(&mut os2, 16, SUBSCRIPT.0);
+        put_i16(&mut os2, 20, SUPERSCRIPT.1);
+        put_i16(&mut os2, 24, SUPERSCRIPT.0);
+        put_i16(&mut os2, 26, STRIKEOUT.1);
+        put_i16(&mut os2, 28, STRIKEOUT.0);
+        vec![(b"OS/2", os2), (b"head", vec![0; 54]), (b"post", post)]
+    }
+
+    #[test]
+    fn font_metrics_use_center_of_lines() {
+        let metrics = FontMetrics::read(&font_data(&[], &tables()));
+        let expected = FontMetrics {
+            underline: Some((-85.0, 90.0)),
+            strikeout: Some((479.0, 102.0)),
+            superscript: Some((983.0, 1433.0)),
+            subscript: Some((286.0, 1433.0)),
+        };
+        assert_eq!(expected, metrics);
+    }
+
+    #[test]
+    fn font_metrics_from_font_collection() {
+        let mut header = b"ttcf\0\x01\0\0\0\0\0\x01".to_vec();
+        header.extend_from_slice(&16u32.to_be_bytes());
+        let metrics = FontMetrics::read(&font_data(&header, &tables()));
+        assert_eq!(Some((-85.0, 90.0)), metrics.underline);
+    }
+
+    #[test]
+    fn font_metrics_without_tables() {
+        let mut tables = tables();
+        tables.retain(|(tag, _)| *tag != b"OS/2");
+        // A line with a thickness of zero is ignored.
+        put_i16(&mut tables[1].1, 10, 0);
+        let metrics = FontMetrics::read(&font_data(&[], &tables));
+        assert_eq!(FontMetrics::default(), metrics);
+    }
+
+    #[test]
+    fn font_metrics_from_invalid_data() {
+        let data = font_data(&[], &tables());
+        // The post table is the last table, so it is incomplete if the data is truncated.
+        for len in 0..data.len() {
+            let metrics = FontMetrics::read(&data[..len]);
+            assert_eq!(None, metrics.underline);
+        }
+        assert_eq!(FontMetrics::default(), FontMetrics::read(&[0xff; 64]));
+    }
+}
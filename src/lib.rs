@@ -92,14 +92,30 @@
 //! a [`RenderResult`][] with `has_more == false`.
 //!
 //! Elements may print to the provided area using the methods of the [`Area`][] struct, or by
-//! calling the `render` method of other elements, or both.
+//! calling the `render` method of other elements, or both.  If an element has to print content
+//! that depends on the layout of the complete document, for example page references, it can
+//! register a callback with [`Area::defer`][] that is called after all elements have been
+//! rendered.
+//!
+//! Footnotes are collected while the page content is rendered, see [`Area::add_footnote`][].  The
+//! space reserved for footnotes is removed from the bottom of the area passed to the elements.
+//! After the page content has been rendered, the footnotes are rendered into the reserved space
+//! above the footer.  Footnotes that do not fit on the page are continued on the next page.
 //!
 //! Every new page is prepared by calling the document’s [`PageDecorator`][] (if set).  This
 //! decorator can add a margin to the page, print a header, a footer, or perform other tasks.
+//! Once all elements have been rendered and the total number of pages is known, the decorator is
+//! called again for every page so that it can add content that depends on the page count, for
+//! example page numbers like “Page 3 of 12”, and so that it can add layers beneath or above the
+//! page content, for example for a [`Watermark`][].
 //!
 //! The render process is cancelled if an `Element` returns an error, or if no content has been
-//! rendered to a newly created page.  This indicates that an element does not fit on a clear page
-//! and can’t even be rendered partially, so the rendering process is cancelled.
+//! rendered to a newly created page even after the `render` method has been called a second time
+//! (unless footnotes have been rendered to the page).
+//! This indicates that an element does not fit on a clear page and can’t even be rendered
+//! partially, so the rendering process is cancelled.  The second call allows elements to move
+//! their content to the next page once without knowing whether they already are at the top of a
+//! page, see [`KeepTogether`][].
 //!
 //! As the [`Element::render`][] method is called repeatedly until the complete element has been
 //! rendered, the element has to keep track of the content that has already been rendered.  As
@@ -132,16 +148,20 @@
 //! [`Document`]: struct.Document.html
 //! [`Document::render`]: struct.Document.html#method.render
 //! [`Document::render_to_file`]: struct.Document.html#method.render_to_file
+//! [`Area::add_footnote`]: render/struct.Area.html#method.add_footnote
 //! [`Document::load_font_family`]: struct.Document.html#method.load_font_family
 //! [`Element`]: trait.Element.html
 //! [`Element::render`]: trait.Element.html#tymethod.render
 //! [`Element::styled`]: trait.Element.html#tymethod.styled
 //! [`PageDecorator`]: trait.PageDecorator.html
+//! [`Watermark`]: struct.Watermark.html
 //! [`RenderResult`]: struct.RenderResult.html
 //! [`LinearLayout`]: elements/struct.LinearLayout.html
 //! [`StyledElement`]: elements/StyledElement.html
 //! [`FontCache`]: fonts/struct.FontCache.html
 //! [`Area`]: render/struct.Area.html
+//! [`KeepTogether`]: elements/struct.KeepTogether.html
+//! [`Area::defer`]: render/struct.Area.html#method.defer
 //! [`Mm`]: struct.Mm.html
 //! [`Size`]: struct.Size.html
 //! [`Position`]: struct.Position.html
@@ -207,6 +227,11 @@ impl Mm {
     pub fn max(self, other: Mm) -> Mm {
         Mm(self.0.max(other.0))
     }
+
+    /// Returns the minimum of this value and the given value.
+    pub fn min(self, other: Mm) -> Mm {
+        Mm(self.0.min(other.0))
+    }
 }
 
 impl From<i8> for Mm {
@@ -337,6 +362,39 @@ impl Size {
         self.height += other.height;
         self
     }
+
+    /// Stacks the given size horizontally on this size and returns the result.
+    ///
+    /// This means that the width is set to the sum of the widths and the height is set to the
+    /// maximum of the heights.
+    #[must_use]
+    pub fn stack_horizontal(mut self, other: Size) -> Size {
+        self.width += other.width;
+        self.height = self.height.max(other.height);
+        self
+    }
+
+    /// Returns this size in landscape orientation, i. e. with a width that is not smaller than the
+    /// height.
+    #[must_use]
+    pub fn landscape(self) -> Size {
+        if self.width < self.height {
+            Size::new(self.height, self.width)
+        } else {
+            self
+        }
+    }
+
+    /// Returns this size in portrait orientation, i. e. with a height that is not smaller than the
+    /// width.
+    #[must_use]
+    pub fn portrait(self) -> Size {
+        if self.height < self.width {
+            Size::new(self.height, self.width)
+        } else {
+            self
+        }
+    }
 }
 
 impl<W: Into<Mm>, H: Into<Mm>> From<(W, H)> for Size {
@@ -345,6 +403,45 @@ impl<W: Into<Mm>, H: Into<Mm>> From<(W, H)> for Size {
     }
 }
 
+/// A scaling factor for the horizontal and vertical axes.
+///
+/// The default scale is 1 for both axes, meaning that nothing is scaled.
+#[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
+pub struct Scale {
+    /// The scaling factor for the x axis.
+    pub x: f64,
+    /// The scaling factor for the y axis.
+    pub y: f64,
+}
+
+impl Scale {
+    /// Creates a new scale from the given factors.
+    pub fn new(x: impl Into<f64>, y: impl Into<f64>) -> Scale {
+        Scale {
+            x: x.into(),
+            y: y.into(),
+        }
+    }
+}
+
+impl Default for Scale {
+    fn default() -> Scale {
+        Scale::new(1, 1)
+    }
+}
+
+impl From<f64> for Scale {
+    fn from(scale: f64) -> Scale {
+        Scale::new(scale, scale)
+    }
+}
+
+impl<X: Into<f64>, Y: Into<f64>> From<(X, Y)> for Scale {
+    fn from(values: (X, Y)) -> Scale {
+        Scale::new(values.0, values.1)
+    }
+}
+
 /// A paper size like A4, legal or letter.
 ///
 /// This enum provides variants for typical paper sizes that can be converted into [`Size`][]
@@ -361,6 +458,20 @@ pub enum PaperSize {
     Letter,
 }
 
+impl PaperSize {
+    /// Returns the size of this paper size in landscape orientation.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use genpdf::{PaperSize, Size};
+    /// assert_eq!(PaperSize::A4.landscape(), Size::new(297, 210));
+    /// ```
+    pub fn landscape(self) -> Size {
+        Size::from(self).landscape()
+    }
+}
+
 impl From<PaperSize> for Size {
     fn from(size: PaperSize) -> Size {
         match size {
@@ -551,9 +662,13 @@ impl Document {
 
     /// Sets the paper size for all pages of this document.
     ///
-    /// If this method is not called, the default size [`A4`][] is used.
+    /// If this method is not called, the default size [`A4`][] is used.  Elements can change the
+    /// size of the following pages, see [`PageBreak::set_size`][] and
+    /// [`Area::set_next_page_size`][].
     ///
     /// [`A4`]: enum.PaperSize.html#variant.A4
+    /// [`PageBreak::set_size`]: elements/struct.PageBreak.html#method.set_size
+    /// [`Area::set_next_page_size`]: render/struct.Area.html#method.set_next_page_size
     pub fn set_paper_size(&mut self, paper_size: impl Into<Size>) {
         self.paper_size = paper_size.into();
     }
@@ -612,22 +727,65 @@ impl Document {
             renderer = renderer.with_conformance(conformance);
         }
         self.context.font_cache.load_pdf_fonts(&renderer)?;
+        // The footnotes that did not fit on the previous page
+        let mut footnotes: Vec<Box<dyn Element>> = Vec::new();
+        let mut paper_size = self.paper_size;
+        let mut is_finished = false;
         loop {
             let mut area = renderer.last_page().last_layer().area();
             if let Some(decorator) = &mut self.decorator {
                 area = decorator.decorate_page(&self.context, area, self.style)?;
             }
-            let result = self.root.render(&self.context, area, self.style)?;
-            if result.has_more {
-                if result.size == Size::new(0, 0) {
-                    return Err(error::Error::new(
-                        "Could not fit an element on a new page",
-                        error::ErrorKind::PageSizeExceeded,
-                    ));
+            area.enable_footnotes();
+            for (idx, footnote) in footnotes.drain(..).enumerate() {
+                let width = area.size().width;
+                let mut height = footnote
+                    .content_height(&self.context, self.style, width)
+                    .unwrap_or_default();
+                if idx == 0 {
+                    height += FOOTNOTE_SEPARATOR;
                 }
-                renderer.add_page(self.paper_size);
-            } else {
+                let max_height = area.size().height;
+                area.add_footnote(footnote, height.min(max_height));
+            }
+
+            let mut result = RenderResult::default();
+            if !is_finished {
+                result = self.root.render(&self.context, area.clone(), self.style)?;
+                if result.has_more && result.size == Size::new(0, 0) {
+                    // Elements may move their content to the next page once, for example if they
+                    // should be kept together.  If this happens on a new page, we render them
+                    // again so that they can split their content instead.
+                    result = self.root.render(&self.context, area.clone(), self.style)?;
+                }
+                is_finished = !result.has_more;
+            }
+            let (remaining, has_footnotes) = render_footnotes(&self.context, &area, self.style)?;
+            footnotes = remaining;
+
+            if is_finished && footnotes.is_empty() {
                 break;
+            } else if result.size == Size::new(0, 0) && !has_footnotes {
+                return Err(error::Error::new(
+                    "Could not fit an element on a new page",
+                    error::ErrorKind::PageSizeExceeded,
+                ));
+            }
+            if let Some(size) = renderer.take_next_page_size() {
+                paper_size = size;
+            }
+            renderer.add_page(paper_size);
+        }
+        renderer.render_deferred(&self.context.font_cache)?;
+        if let Some(decorator) = &mut self.decorator {
+            let page_count = renderer.page_count();
+            for idx in 0..page_count {
+                let page = renderer
+                    .get_page_mut(idx)
+                    .expect("Could not find a page of the document");
+                let area = page.last_layer().area();
+                decorator.finish_page(&self.context, area, self.style, idx + 1, page_count)?;
+                decorator.decorate_layers(&self.context, page, self.style, idx + 1, page_count)?;
             }
         }
         renderer.write(w)
@@ -647,6 +805,45 @@ impl Document {
     }
 }
 
+/// The height of the separator between the page content and the footnotes.
+const FOOTNOTE_SEPARATOR: Mm = Mm(5.0);
+
+/// Renders the footnotes that have been added to the page of the given area into its footnote
+/// area.
+///
+/// This function returns the footnotes that did not fit on the page and whether any footnote
+/// content has been rendered.
+fn render_footnotes(
+    context: &Context,
+    area: &render::Area<'_>,
+    style: style::Style,
+) -> Result<(Vec<Box<dyn Element>>, bool), error::Error> {
+    let mut footnotes = area.take_footnotes();
+    let mut footnote_area = match area.footnote_area() {
+        Some(footnote_area) if !footnotes.is_empty() => footnote_area,
+        _ => return Ok((footnotes, false)),
+    };
+
+    let y = FOOTNOTE_SEPARATOR / 2.0;
+    let width = footnote_area.size().width / 3.0;
+    footnote_area.draw_line(vec![Position::new(0, y), Position::new(width, y)], style);
+    footnote_area.add_offset(Position::new(0, FOOTNOTE_SEPARATOR));
+
+    let mut is_rendered = false;
+    let mut count = 0;
+    for footnote in &mut footnotes {
+        let result = footnote.render(context, footnote_area.clone(), style)?;
+        footnote_area.add_offset(Position::new(0, result.size.height));
+        is_rendered |= result.size != Size::new(0, 0);
+        if result.has_more {
+            break;
+        }
+        count += 1;
+    }
+    footnotes.drain(..count);
+    Ok((footnotes, is_rendered))
+}
+
 /// The result of the rendering process.
 ///
 /// This struct is returned by implementations of the [`Element::render`][] method.  It contains
@@ -667,17 +864,79 @@ pub struct RenderResult {
     pub has_more: bool,
 }
 
+/// The minimum and maximum width of the content of an element.
+///
+/// This struct is returned by implementations of the [`Element::content_width`][] method.  The
+/// minimum width is the smallest width that the element can be rendered in without truncating its
+/// content, for example the width of the longest word of a paragraph.  The maximum width is the
+/// width that the element would use if it had unlimited horizontal space, for example the width of
+/// a paragraph without line breaks.
+///
+/// [`Element::content_width`]: trait.Element.html#method.content_width
+#[derive(Clone, Copy, Debug, Default, PartialEq, PartialOrd)]
+pub struct ContentWidth {
+    /// The minimum width of the content.
+    pub min: Mm,
+    /// The maximum width of the content.
+    pub max: Mm,
+}
+
+impl ContentWidth {
+    /// Creates a new content width from the given minimum and maximum width.
+    pub fn new(min: impl Into<Mm>, max: impl Into<Mm>) -> ContentWidth {
+        ContentWidth {
+            min: min.into(),
+            max: max.into(),
+        }
+    }
+
+    /// Creates a new content width with the same minimum and maximum width.
+    pub fn fixed(width: impl Into<Mm>) -> ContentWidth {
+        let width = width.into();
+        ContentWidth::new(width, width)
+    }
+
+    /// Stacks the given content width vertically on this content width and returns the result.
+    ///
+    /// This means that the minimum and maximum widths are set to the maximum of the respective
+    /// widths.
+    #[must_use]
+    pub fn stack_vertical(self, other: ContentWidth) -> ContentWidth {
+        ContentWidth::new(self.min.max(other.min), self.max.max(other.max))
+    }
+
+    /// Stacks the given content width horizontally on this content width and returns the result.
+    ///
+    /// This means that the minimum and maximum widths are set to the sum of the respective widths.
+    #[must_use]
+    pub fn stack_horizontal(self, other: ContentWidth) -> ContentWidth {
+        ContentWidth::new(self.min + other.min, self.max + other.max)
+    }
+}
+
+impl std::ops::Add<Mm> for ContentWidth {
+    type Output = ContentWidth;
+
+    fn add(self, width: Mm) -> ContentWidth {
+        ContentWidth::new(self.min + width, self.max + width)
+    }
+}
+
 /// Prepares a page of a document.
 ///
 /// If you set an implementation of this trait for a [`Document`][] using the
 /// [`set_page_decorator`][] method, its [`decorate_page`][] method is called every time a new page
 /// is added to the document.  The decorator can prepare the page before it is filled with the
-/// actual content.  See [`SimplePageDecorator`][] for a basic implementation.
+/// actual content.  After all elements have been rendered, the [`finish_page`][] and the
+/// [`decorate_layers`][] methods are called once for every page.  See [`SimplePageDecorator`][]
+/// for a basic implementation.
 ///
 /// [`Document`]: struct.Document.html
 /// [`set_page_decorator`]: struct.Document.html#method.set_page_decorator
 /// [`SimplePageDecorator`]: struct.SimplePageDecorator.html
 /// [`decorate_page`]: #tymethod.decorate_page
+/// [`finish_page`]: #method.finish_page
+/// [`decorate_layers`]: #method.decorate_layers
 pub trait PageDecorator {
     /// Prepares the page with the given area before it is filled with the document content and
     /// returns the writable area of the page.
@@ -689,24 +948,312 @@ pub trait PageDecorator {
         area: render::Area<'a>,
         style: style::Style,
     ) -> Result<render::Area<'a>, error::Error>;
+
+    /// Finishes the page with the given area and page number (starting with 1) after the document
+    /// content has been rendered.
+    ///
+    /// This method is called once for every page after all elements have been rendered.  The area
+    /// is the full area of the page, and the page count is the total number of pages of the
+    /// document.  It can be used to print content that depends on the page count, like “Page 3 of
+    /// 12”.  Note that the content has to be printed in space that has been reserved by
+    /// [`decorate_page`][].  The default implementation does nothing.
+    ///
+    /// [`decorate_page`]: #tymethod.decorate_page
+    fn finish_page(
+        &mut self,
+        context: &Context,
+        area: render::Area<'_>,
+        style: style::Style,
+        page: usize,
+        page_count: usize,
+    ) -> Result<(), error::Error> {
+        let _ = (context, area, style, page, page_count);
+        Ok(())
+    }
+
+    /// Decorates the layers of the given page with the given page number (starting with 1) after
+    /// the document content has been rendered.
+    ///
+    /// This method is called once for every page after [`finish_page`][].  In contrast to the
+    /// other methods, it has access to the full page, so it can add new layers beneath or above
+    /// the document content with [`Page::insert_layer`][] and [`Page::add_layer`][], for example
+    /// for watermarks.  The default implementation does nothing.
+    ///
+    /// [`finish_page`]: #method.finish_page
+    /// [`Page::insert_layer`]: render/struct.Page.html#method.insert_layer
+    /// [`Page::add_layer`]: render/struct.Page.html#method.add_layer
+    fn decorate_layers(
+        &mut self,
+        context: &Context,
+        page: &mut render::Page,
+        style: style::Style,
+        page_number: usize,
+        page_count: usize,
+    ) -> Result<(), error::Error> {
+        let _ = (context, page, style, page_number, page_count);
+        Ok(())
+    }
 }
 
-type HeaderCallback = Box<dyn Fn(usize) -> Box<dyn Element>>;
+type DecorationCallback = Box<dyn Fn(usize, usize) -> Box<dyn Element>>;
 
-/// Prepares a page of a document with margins and a header.
+/// A header or footer of a [`SimplePageDecorator`][].
+///
+/// [`SimplePageDecorator`]: struct.SimplePageDecorator.html
+struct Decoration {
+    cb: DecorationCallback,
+    // If set, the decoration is only measured in decorate_page and rendered in finish_page.
+    needs_page_count: bool,
+}
+
+impl Decoration {
+    fn new(cb: DecorationCallback, needs_page_count: bool) -> Decoration {
+        Decoration {
+            cb,
+            needs_page_count,
+        }
+    }
+
+    /// Creates the element for the given page.
+    ///
+    /// If the page count is not known yet, we have to guess it.  Usually, the height of the element
+    /// does not depend on it.
+    fn create_element(&self, page: usize, page_count: Option<usize>) -> Box<dyn Element> {
+        (self.cb)(page, page_count.unwrap_or(page))
+    }
+}
+
+fn measure_decoration(
+    element: &dyn Element,
+    context: &Context,
+    style: style::Style,
+    width: Mm,
+) -> Result<Mm, error::Error> {
+    element
+        .content_height(context, style, width)
+        .ok_or_else(|| {
+            error::Error::new(
+                "Could not measure the height of a page decoration",
+                error::ErrorKind::InvalidData,
+            )
+        })
+}
+
+type WatermarkCallback = Box<dyn Fn(usize, usize) -> Box<dyn Element>>;
+
+/// A watermark or stamp that is drawn across a page, for example “DRAFT” or “CONFIDENTIAL”.
+///
+/// Watermarks can be added to a document with [`SimplePageDecorator::set_watermark`][].  The
+/// watermark element is centered at the center of the page (or at the configured position), and
+/// rotated counter-clockwise by the configured angle around this point.  It is drawn on a separate
+/// layer, either beneath the page content (the default) or above it.  The watermark does not
+/// depend on the margins of the page and it does not take away space from the page content.
+///
+/// The color, the font and the opacity of the watermark can be set with its style, see
+/// [`Style::set_opacity`][].  The size of the watermark element is measured with
+/// [`Element::content_width`][] and [`Element::content_height`][].  If the element does not
+/// support these methods, it is rendered in the full page area before it is rotated.
+///
+/// # Example
+///
+/// ```
+/// use genpdf::style;
+/// let watermark = genpdf::Watermark::text("DRAFT")
+///     .with_rotation(45.0)
+///     .with_style(
+///         style::Style::new()
+///             .bold()
+///             .with_font_size(96)
+///             .with_color(style::Color::Rgb(255, 0, 0))
+///             .with_opacity(0.2),
+///     );
+/// let mut decorator = genpdf::SimplePageDecorator::new();
+/// decorator.set_watermark(watermark);
+/// ```
+///
+/// [`SimplePageDecorator::set_watermark`]: struct.SimplePageDecorator.html#method.set_watermark
+/// [`Style::set_opacity`]: style/struct.Style.html#method.set_opacity
+/// [`Element::content_width`]: trait.Element.html#method.content_width
+/// [`Element::content_height`]: trait.Element.html#method.content_height
+pub struct Watermark {
+    cb: WatermarkCallback,
+    style: style::Style,
+    rotation: f64,
+    position: Option<Position>,
+    is_foreground: bool,
+}
+
+impl Watermark {
+    /// Creates a new watermark from the given element generator.
+    ///
+    /// The given closure will be called once per page.  Its arguments are the page number
+    /// (starting with 1) and the total number of pages, and its return value will be rendered as
+    /// the watermark.
+    pub fn new<F, E>(cb: F) -> Watermark
+    where
+        F: Fn(usize, usize) -> E + 'static,
+        E: Element + 'static,
+    {
+        Watermark {
+            cb: Box::new(move |page, page_count| Box::new(cb(page, page_count))),
+            style: style::Style::new(),
+            rotation: 0.0,
+            position: None,
+            is_foreground: false,
+        }
+    }
+
+    /// Creates a new watermark that prints the given text.
+    pub fn text(text: impl Into<style::StyledString>) -> Watermark {
+        let text = text.into();
+        Watermark::new(move |_, _| elements::Text::new(text.clone()))
+    }
+
+    /// Creates a new watermark that shows the given image.
+    ///
+    /// *Only available if the `images` feature is enabled.*
+    #[cfg(feature = "images")]
+    pub fn image(image: elements::Image) -> Watermark {
+        Watermark::new(move |_, _| image.clone())
+    }
+
+    /// Sets the style of this watermark.
+    pub fn set_style(&mut self, style: impl Into<style::Style>) {
+        self.style = style.into();
+    }
+
+    /// Sets the style of this watermark and returns it.
+    pub fn with_style(mut self, style: impl Into<style::Style>) -> Watermark {
+        self.set_style(style);
+        self
+    }
+
+    /// Sets the counter-clockwise rotation of this watermark in degrees.
+    pub fn set_rotation(&mut self, rotation: f64) {
+        self.rotation = rotation;
+    }
+
+    /// Sets the counter-clockwise rotation of this watermark in degrees and returns it.
+    pub fn with_rotation(mut self, rotation: f64) -> Watermark {
+        self.set_rotation(rotation);
+        self
+    }
+
+    /// Sets the position of the center of this watermark, relative to the upper left corner of
+    /// the page.
+    ///
+    /// If this method is not called, the watermark is centered on the page.
+    pub fn set_position(&mut self, position: impl Into<Position>) {
+        self.position = Some(position.into());
+    }
+
+    /// Sets the position of the center of this watermark, relative to the upper left corner of
+    /// the page, and returns it.
+    pub fn with_position(mut self, position: impl Into<Position>) -> Watermark {
+        self.set_position(position);
+        self
+    }
+
+    /// Sets whether this watermark is drawn above the page content instead of beneath it.
+    pub fn set_foreground(&mut self, is_foreground: bool) {
+        self.is_foreground = is_foreground;
+    }
+
+    /// Sets whether this watermark is drawn above the page content instead of beneath it and
+    /// returns it.
+    pub fn with_foreground(mut self, is_foreground: bool) -> Watermark {
+        self.set_foreground(is_foreground);
+        self
+    }
+
+    /// Renders this watermark on a new layer of the given page.
+    fn render(
+        &self,
+        context: &Context,
+        page: &mut render::Page,
+        style: style::Style,
+        page_number: usize,
+        page_count: usize,
+    ) -> Result<(), error::Error> {
+        let idx = if self.is_foreground {
+            page.layer_count()
+        } else {
+            0
+        };
+        page.insert_layer(idx, "Watermark");
+        let mut area = page
+            .get_layer(idx)
+            .expect("Could not find the watermark layer")
+            .area();
+
+        let style = style.and(self.style);
+        let mut element = (self.cb)(page_number, page_count);
+        let page_size = area.size();
+        let width = element
+            .content_width(context, style)
+            .map(|width| width.max)
+            .unwrap_or(page_size.width);
+        let height = element
+            .content_height(context, style, width)
+            .unwrap_or(page_size.height);
+        let center = self
+            .position
+            .unwrap_or_else(|| Position::new(page_size.width / 2.0, page_size.height / 2.0));
+        area.add_offset(center - Position::new(width / 2.0, height / 2.0));
+        area.set_size(Size::new(width, height));
+        let center = Position::new(width / 2.0, height / 2.0);
+        let transform = render::Transform::rotate(self.rotation);
+        area.transformed(center, transform, |area| {
+            element.render(context, area.clone(), style)
+        })?;
+        Ok(())
+    }
+}
+
+/// Prepares a page of a document with margins, a header and a footer.
 ///
 /// Per default, this decorator does not modify the page.  If margins have been set with the
 /// [`set_margins`][] method, they are applied to every page.  If a header callback is configured
-/// with the [`set_header`][] method, it will be called for every page and its return value will be
-/// rendered at the beginning of the page (after the margins have been applied).
+/// with the [`set_header`][] or [`set_header_with_page_count`][] method, it will be called for
+/// every page and its return value will be rendered at the beginning of the page (after the
+/// margins have been applied).  Similarly, if a footer callback is configured with the
+/// [`set_footer`][] or [`set_footer_with_page_count`][] method, its return value will be rendered
+/// at the end of the page, directly above the bottom margin.  The space for the footer is reserved
+/// before the document content is rendered, so the content never overlaps the footer.  A
+/// [`Watermark`][] set with [`set_watermark`][] is drawn on every page beneath or above the
+/// content.
+///
+/// # Example
+///
+/// ```
+/// use genpdf::{elements, Element as _};
+/// let mut decorator = genpdf::SimplePageDecorator::new();
+/// decorator.set_margins(10);
+/// decorator.set_header(|_| {
+///     elements::Paragraph::new("Annual Report")
+///         .padded(genpdf::Margins::trbl(0, 0, 5, 0))
+/// });
+/// decorator.set_footer_with_page_count(|page, page_count| {
+///     elements::Paragraph::new(format!("Page {} of {}", page, page_count))
+///         .aligned(elements::Alignment::Right)
+///         .padded(genpdf::Margins::trbl(5, 0, 0, 0))
+/// });
+/// ```
 ///
 /// [`set_margins`]: #method.set_margins
 /// [`set_header`]: #method.set_header
+/// [`set_header_with_page_count`]: #method.set_header_with_page_count
+/// [`set_footer`]: #method.set_footer
+/// [`set_footer_with_page_count`]: #method.set_footer_with_page_count
+/// [`set_watermark`]: #method.set_watermark
+/// [`Watermark`]: struct.Watermark.html
 #[derive(Default)]
 pub struct SimplePageDecorator {
     page: usize,
     margins: Option<Margins>,
-    header_cb: Option<HeaderCallback>,
+    header: Option<Decoration>,
+    footer: Option<Decoration>,
+    watermark: Option<Watermark>,
 }
 
 impl SimplePageDecorator {
@@ -733,7 +1280,71 @@ impl SimplePageDecorator {
         E: Element + 'static,
     {
         // We manually box the return type of the callback so that it is easier to write closures.
-        self.header_cb = Some(Box::new(move |page| Box::new(cb(page))));
+        let cb = Box::new(move |page, _| Box::new(cb(page)) as Box<dyn Element>);
+        self.header = Some(Decoration::new(cb, false));
+    }
+
+    /// Sets a header generator that has access to the total number of pages for this document.
+    ///
+    /// The given closure will be called once per page.  Its arguments are the page number
+    /// (starting with 1) and the total number of pages, and its return value will be rendered at
+    /// the top of the page.  The document content will start directly after the element.
+    ///
+    /// As the page count is only known after the document content has been rendered, the element
+    /// is rendered after all other elements.  To reserve the space for the header, the closure is
+    /// called with a preliminary page count, and the height of the returned element is measured
+    /// with [`Element::content_height`][].  Therefore, the height of the header must not depend on
+    /// the page count, and the element must support `content_height`.
+    ///
+    /// [`Element::content_height`]: trait.Element.html#method.content_height
+    pub fn set_header_with_page_count<F, E>(&mut self, cb: F)
+    where
+        F: Fn(usize, usize) -> E + 'static,
+        E: Element + 'static,
+    {
+        let cb =
+            Box::new(move |page, page_count| Box::new(cb(page, page_count)) as Box<dyn Element>);
+        self.header = Some(Decoration::new(cb, true));
+    }
+
+    /// Sets the footer generator for this document.
+    ///
+    /// The given closure will be called once per page.  Its argument is the page number (starting
+    /// with 1), and its return value will be rendered at the bottom of the page.  The height of
+    /// the footer is measured with [`Element::content_height`][] and reserved before the document
+    /// content is rendered, so the element must support `content_height`.
+    ///
+    /// [`Element::content_height`]: trait.Element.html#method.content_height
+    pub fn set_footer<F, E>(&mut self, cb: F)
+    where
+        F: Fn(usize) -> E + 'static,
+        E: Element + 'static,
+    {
+        let cb = Box::new(move |page, _| Box::new(cb(page)) as Box<dyn Element>);
+        self.footer = Some(Decoration::new(cb, false));
+    }
+
+    /// Sets a footer generator that has access to the total number of pages for this document.
+    ///
+    /// The given closure will be called once per page.  Its arguments are the page number
+    /// (starting with 1) and the total number of pages, and its return value will be rendered at
+    /// the bottom of the page.  See [`set_header_with_page_count`][] for more information on
+    /// the rendering of elements that use the page count.
+    ///
+    /// [`set_header_with_page_count`]: #method.set_header_with_page_count
+    pub fn set_footer_with_page_count<F, E>(&mut self, cb: F)
+    where
+        F: Fn(usize, usize) -> E + 'static,
+        E: Element + 'static,
+    {
+        let cb =
+            Box::new(move |page, page_count| Box::new(cb(page, page_count)) as Box<dyn Element>);
+        self.footer = Some(Decoration::new(cb, true));
+    }
+
+    /// Sets the watermark for all pages of this document.
+    pub fn set_watermark(&mut self, watermark: Watermark) {
+        self.watermark = Some(watermark);
     }
 }
 
@@ -748,13 +1359,69 @@ impl PageDecorator for SimplePageDecorator {
         if let Some(margins) = self.margins {
             area.add_margins(margins);
         }
-        if let Some(cb) = &self.header_cb {
-            let mut element = cb(self.page);
-            let result = element.render(context, area.clone(), style)?;
-            area.add_offset(Position::new(0, result.size.height));
+        if let Some(footer) = &self.footer {
+            let mut element = footer.create_element(self.page, None);
+            let height = measure_decoration(&*element, context, style, area.size().width)?;
+            if !footer.needs_page_count {
+                let mut footer_area = area.clone();
+                footer_area.add_offset(Position::new(0, area.size().height - height));
+                element.render(context, footer_area, style)?;
+            }
+            area.set_height(area.size().height - height);
+        }
+        if let Some(header) = &self.header {
+            let mut element = header.create_element(self.page, None);
+            let height = if header.needs_page_count {
+                measure_decoration(&*element, context, style, area.size().width)?
+            } else {
+                element.render(context, area.clone(), style)?.size.height
+            };
+            area.add_offset(Position::new(0, height));
         }
         Ok(area)
     }
+
+    fn finish_page(
+        &mut self,
+        context: &Context,
+        mut area: render::Area<'_>,
+        style: style::Style,
+        page: usize,
+        page_count: usize,
+    ) -> Result<(), error::Error> {
+        if let Some(margins) = self.margins {
+            area.add_margins(margins);
+        }
+        if let Some(header) = &self.header {
+            if header.needs_page_count {
+                let mut element = header.create_element(page, Some(page_count));
+                element.render(context, area.clone(), style)?;
+            }
+        }
+        if let Some(footer) = &self.footer {
+            if footer.needs_page_count {
+                let mut element = footer.create_element(page, Some(page_count));
+                let height = measure_decoration(&*element, context, style, area.size().width)?;
+                area.add_offset(Position::new(0, area.size().height - height));
+                element.render(context, area, style)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn decorate_layers(
+        &mut self,
+        context: &Context,
+        page: &mut render::Page,
+        style: style::Style,
+        page_number: usize,
+        page_count: usize,
+    ) -> Result<(), error::Error> {
+        if let Some(watermark) = &self.watermark {
+            watermark.render(context, page, style, page_number, page_count)?;
+        }
+        Ok(())
+    }
 }
 
 /// An element of a PDF document.
@@ -809,6 +1476,49 @@ pub trait Element {
         style: style::Style,
     ) -> Result<RenderResult, error::Error>;
 
+    /// Measures the minimum and maximum width of the content of this element using the given style
+    /// and context.
+    ///
+    /// This method is used by containers that have to know the size of their children before
+    /// rendering them, for example to size columns to fit their content.  It must not have side
+    /// effects and should only consider the content that has not been rendered yet.  The default
+    /// implementation returns `None`, meaning that the content width is unknown.
+    fn content_width(&self, context: &Context, style: style::Style) -> Option<ContentWidth> {
+        let _ = (context, style);
+        None
+    }
+
+    /// Measures the height of the content of this element if it is rendered in an area with the
+    /// given width, using the given style and context.
+    ///
+    /// The returned height is the height the element would use if the area was unlimited in its
+    /// vertical direction.  Just like [`content_width`][], this method must not have side effects
+    /// and should only consider the content that has not been rendered yet.  The default
+    /// implementation returns `None`, meaning that the content height is unknown.
+    ///
+    /// [`content_width`]: #method.content_width
+    fn content_height(&self, context: &Context, style: style::Style, width: Mm) -> Option<Mm> {
+        let _ = (context, style, width);
+        None
+    }
+
+    /// Returns whether this element should be kept on the same page as the next element.
+    ///
+    /// If this method returns true, a vertical [`LinearLayout`][] moves this element to the next
+    /// page if it does not fit on the current page together with the first line of the next
+    /// element.  This requires that the content height of the elements is known, see
+    /// [`content_height`][].  The default implementation returns false.  Wrapper elements should
+    /// return the value of the wrapped element.
+    ///
+    /// To keep an element with the next element, use [`keep_with_next`][].
+    ///
+    /// [`LinearLayout`]: elements/struct.LinearLayout.html
+    /// [`content_height`]: #method.content_height
+    /// [`keep_with_next`]: #method.keep_with_next
+    fn keeps_with_next(&self) -> bool {
+        false
+    }
+
     /// Draws a frame around this element.
     fn framed(self) -> elements::FramedElement<Self>
     where
@@ -817,6 +1527,19 @@ pub trait Element {
         elements::FramedElement::new(self)
     }
 
+    /// Draws a box with the given decoration around this element, see [`DecoratedElement`][].
+    ///
+    /// [`DecoratedElement`]: elements/struct.DecoratedElement.html
+    fn decorated(
+        self,
+        decoration: impl Into<style::BoxDecoration>,
+    ) -> elements::DecoratedElement<Self>
+    where
+        Self: Sized,
+    {
+        elements::DecoratedElement::new(self, decoration)
+    }
+
     /// Adds a padding to this element.
     fn padded(self, padding: impl Into<Margins>) -> elements::PaddedElement<Self>
     where
@@ -832,6 +1555,40 @@ pub trait Element {
     {
         elements::StyledElement::new(self, style.into())
     }
+
+    /// Adds a named destination with the given name at the position of this element.
+    fn anchored(self, name: impl Into<String>) -> elements::Anchor<Self>
+    where
+        Self: Sized,
+    {
+        elements::Anchor::new(self, name)
+    }
+
+    /// Moves this element to the next page if it would be split across pages.
+    fn keep_together(self) -> elements::KeepTogether<Self>
+    where
+        Self: Sized,
+    {
+        elements::KeepTogether::new(self)
+    }
+
+    /// Keeps this element on the same page as the next element.
+    fn keep_with_next(self) -> elements::KeepWithNext<Self>
+    where
+        Self: Sized,
+    {
+        elements::KeepWithNext::new(self)
+    }
+
+    /// Applies the given transformation to this element, see [`Transformed`][].
+    ///
+    /// [`Transformed`]: elements/struct.Transformed.html
+    fn transformed(self, transform: render::Transform) -> elements::Transformed<Self>
+    where
+        Self: Sized,
+    {
+        elements::Transformed::new(self, transform)
+    }
 }
 
 /// The context for a rendering process.
@@ -5,25 +5,42 @@
 //!
 //! This module provides low-level abstractions over [`printpdf`][]:  A [`Renderer`][] creates a
 //! document with one or more pages with different sizes.  A [`Page`][] has one or more layers, all
-//! of the same size.  A [`Layer`][] can be used to access its [`Area`][].
+//! of the same size, that are drawn on top of each other.  A [`Layer`][] can be used to access its
+//! [`Area`][].
 //!
 //! An [`Area`][] is a view on a full layer or on a part of a layer.  It can be used to print
 //! lines and text.  For more advanced text formatting, you can create a [`TextSection`][] from an
-//! [`Area`][].
+//! [`Area`][].  Shapes can be drawn with the `draw_*` methods of an [`Area`][], for example
+//! [`Area::draw_rectangle`][] or [`Area::draw_path`][].  Backgrounds that are drawn beneath the
+//! content of an [`Area`][] can be registered with [`Area::defer_background`][].  Content can be
+//! rotated, scaled and skewed with [`Area::transformed`][] and a [`Transform`][].  If footnotes
+//! are enabled for an [`Area`][], space can be reserved at its bottom for the footnotes of the
+//! current page.
 //!
 //! [`printpdf`]: https://docs.rs/printpdf/latest/printpdf
 //! [`Renderer`]: struct.Renderer.html
 //! [`Page`]: struct.Page.html
 //! [`Layer`]: struct.Layer.html
 //! [`Area`]: struct.Area.html
+//! [`Area::draw_rectangle`]: struct.Area.html#method.draw_rectangle
+//! [`Area::draw_path`]: struct.Area.html#method.draw_path
+//! [`Area::defer_background`]: struct.Area.html#method.defer_background
+//! [`Area::transformed`]: struct.Area.html#method.transformed
+//! [`Transform`]: struct.Transform.html
 //! [`TextSection`]: struct.TextSection.html
 
+mod postprocess;
+
+use std::cell;
+use std::collections;
 use std::io;
+use std::mem;
+use std::rc;
 
 use crate::error::{Context as _, Error, ErrorKind};
 use crate::fonts;
-use crate::style::{Color, Style};
-use crate::{Margins, Mm, Position, Size};
+use crate::style::{BlendMode, Color, LineCap, LineJoin, LineStyle, Link, ShapeStyle, Style};
+use crate::{Element, Margins, Mm, Position, Size};
 
 /// Renders a PDF document with one or more pages.
 ///
@@ -34,6 +51,7 @@ pub struct Renderer {
     doc: printpdf::PdfDocumentReference,
     // invariant: pages.len() >= 1
     pages: Vec<Page>,
+    data: rc::Rc<cell::RefCell<SharedData>>,
 }
 
 impl Renderer {
@@ -48,11 +66,13 @@ impl Renderer {
         );
         let page_ref = doc.get_page(page_idx);
         let layer_ref = page_ref.get_layer(layer_idx);
-        let page = Page::new(page_ref, layer_ref, size);
+        let data = rc::Rc::new(cell::RefCell::new(SharedData::default()));
+        let page = Page::new(page_ref, layer_ref, size, 0, data.clone());
 
         Ok(Renderer {
             doc,
             pages: vec![page],
+            data,
         })
     }
 
@@ -70,7 +90,17 @@ impl Renderer {
                 .add_page(size.width.into(), size.height.into(), "Layer 1");
         let page_ref = self.doc.get_page(page_idx);
         let layer_ref = page_ref.get_layer(layer_idx);
-        self.pages.push(Page::new(page_ref, layer_ref, size))
+        let idx = self.pages.len();
+        self.pages
+            .push(Page::new(page_ref, layer_ref, size, idx, self.data.clone()))
+    }
+
+    /// Returns the page size that has been requested with [`Area::set_next_page_size`][] since
+    /// the last call of this method, if any.
+    ///
+    /// [`Area::set_next_page_size`]: struct.Area.html#method.set_next_page_size
+    pub fn take_next_page_size(&mut self) -> Option<Size> {
+        self.data.borrow_mut().next_page_size.take()
     }
 
     /// Returns the number of pages in this document.
@@ -78,6 +108,11 @@ impl Renderer {
         self.pages.len()
     }
 
+    /// Returns an iterator over the pages of this document.
+    pub fn pages(&self) -> impl Iterator<Item = &Page> + '_ {
+        self.pages.iter()
+    }
+
     /// Returns a page of this document.
     pub fn get_page(&self, idx: usize) -> Option<&Page> {
         self.pages.get(idx)
@@ -128,24 +163,157 @@ impl Renderer {
             .context("Failed to load PDF font")
     }
 
+    /// Calls the callbacks that have been registered with [`Area::defer`][] and
+    /// [`Area::defer_background`][] for the areas of this document.
+    ///
+    /// This method should be called after all elements have been rendered.  If a callback
+    /// registers new callbacks, they are also executed.
+    ///
+    /// [`Area::defer`]: struct.Area.html#method.defer
+    /// [`Area::defer_background`]: struct.Area.html#method.defer_background
+    pub fn render_deferred(&mut self, font_cache: &fonts::FontCache) -> Result<(), Error> {
+        loop {
+            let deferred = mem::take(&mut self.data.borrow_mut().deferred);
+            let backgrounds = mem::take(&mut self.data.borrow_mut().backgrounds);
+            if deferred.is_empty() && backgrounds.is_empty() {
+                return Ok(());
+            }
+            for deferred in deferred {
+                let layer = self
+                    .get_page(deferred.page_idx)
+                    .and_then(|page| page.find_layer(deferred.layer_idx))
+                    .ok_or_else(deferred_error)?;
+                deferred.call(font_cache, layer)?;
+            }
+            // The background of a container is registered after the backgrounds of its children,
+            // so we draw them in reverse order.
+            for background in backgrounds.into_iter().rev() {
+                let page = self
+                    .get_page_mut(background.page_idx)
+                    .ok_or_else(deferred_error)?;
+                let layer_idx = page
+                    .background_layer(background.layer_idx)
+                    .ok_or_else(deferred_error)?;
+                let layer = page.find_layer(layer_idx).ok_or_else(deferred_error)?;
+                background.call(font_cache, layer)?;
+            }
+        }
+    }
+
     /// Writes this PDF document to a writer.
+    ///
+    /// If elements added features that are not supported by `printpdf`, for example outline
+    /// entries, the document generated by `printpdf` is post-processed with `lopdf` before it is
+    /// written.
     pub fn write(self, w: impl io::Write) -> Result<(), Error> {
+        for page in &self.pages {
+            if let Some(order) = page.layer_order() {
+                let mut data = self.data.borrow_mut();
+                data.document.set_layer_order(page.idx, order);
+            }
+        }
+
+        let data = &self.data.borrow().document;
+        if data.is_empty() {
+            return self
+                .doc
+                .save(&mut io::BufWriter::new(w))
+                .context("Failed to save document");
+        }
+
+        let mut buf = Vec::new();
         self.doc
-            .save(&mut io::BufWriter::new(w))
+            .save(&mut io::BufWriter::new(&mut buf))
+            .context("Failed to save document")?;
+        let mut doc = lopdf::Document::load_mem(&buf).context("Failed to load document")?;
+        let page_sizes: Vec<_> = self.pages.iter().map(|page| page.size).collect();
+        data.apply(&mut doc, &page_sizes)?;
+        doc.save_to(&mut io::BufWriter::new(w))
             .context("Failed to save document")
     }
 }
 
+/// Data that is collected during the rendering process and shared between the renderer, its pages
+/// and their layers.
+#[derive(Default)]
+struct SharedData {
+    document: postprocess::DocumentData,
+    deferred: Vec<Deferred>,
+    backgrounds: Vec<Deferred>,
+    footnotes: collections::HashMap<usize, Footnotes>,
+    footnote_count: usize,
+    next_page_size: Option<Size>,
+}
+
+/// The footnotes that have been added to a page with [`Area::add_footnote`][].
+///
+/// [`Area::add_footnote`]: struct.Area.html#method.add_footnote
+#[derive(Default)]
+struct Footnotes {
+    height: Mm,
+    elements: Vec<Box<dyn Element>>,
+}
+
+/// A callback that has been registered with [`Area::defer`][] or [`Area::defer_background`][].
+///
+/// [`Area::defer`]: struct.Area.html#method.defer
+/// [`Area::defer_background`]: struct.Area.html#method.defer_background
+struct Deferred {
+    page_idx: usize,
+    layer_idx: usize,
+    origin: Position,
+    size: Size,
+    transforms: Vec<(Position, Transform)>,
+    callback: DeferredCallback,
+}
+
+impl Deferred {
+    /// Calls the callback of this deferred area with an area on the given layer.
+    fn call(self, font_cache: &fonts::FontCache, layer: &Layer) -> Result<(), Error> {
+        for (origin, transform) in &self.transforms {
+            layer.set_transform(*origin, *transform);
+        }
+        let mut area = Area::new(layer, self.origin, self.size);
+        area.transforms = self.transforms;
+        let n = area.transforms.len();
+        let result = (self.callback)(font_cache, area);
+        for _ in 0..n {
+            layer.layer.restore_graphics_state();
+        }
+        result
+    }
+}
+
+fn deferred_error() -> Error {
+    Error::new(
+        "Could not find the layer of a deferred area",
+        ErrorKind::Internal,
+    )
+}
+
+type DeferredCallback = Box<dyn FnOnce(&fonts::FontCache, Area<'_>) -> Result<(), Error>>;
+
 /// A page of a PDF document.
 ///
 /// This is a wrapper around a [`printpdf::PdfPageReference`][].
 ///
+/// A page has one or more layers.  The layers are drawn in the order of their indices, so a layer
+/// is drawn above all layers with lower indices.  New layers can be added on top of the existing
+/// layers with [`add_layer`][] or at any position with [`insert_layer`][].
+///
 /// [`printpdf::PdfPageReference`]: https://docs.rs/printpdf/0.3.2/printpdf/types/pdf_page/struct.PdfPageReference.html
+/// [`add_layer`]: #method.add_layer
+/// [`insert_layer`]: #method.insert_layer
 pub struct Page {
     page: printpdf::PdfPageReference,
     size: Size,
+    idx: usize,
     // invariant: layers.len() >= 1
+    // the layers in the order in which they are drawn
     layers: Vec<Layer>,
+    // the indices of the background layers created for layers, see Area::defer_background
+    backgrounds: collections::HashMap<usize, usize>,
+    data: rc::Rc<cell::RefCell<SharedData>>,
 }
 
 impl Page {
@@ -153,18 +321,38 @@ impl Page {
         page: printpdf::PdfPageReference,
         layer: printpdf::PdfLayerReference,
         size: Size,
+        idx: usize,
+        data: rc::Rc<cell::RefCell<SharedData>>,
     ) -> Page {
         Page {
             page,
             size,
-            layers: vec![Layer::new(layer, size)],
+            idx,
+            layers: vec![Layer::new(layer, size, idx, 0, data.clone())],
+            backgrounds: collections::HashMap::new(),
+            data,
         }
     }
 
-    /// Adds a new layer with the given name to the page.
+    /// Adds a new layer with the given name on top of the existing layers of this page.
     pub fn add_layer(&mut self, name: impl Into<String>) {
+        self.insert_layer(self.layers.len(), name);
+    }
+
+    /// Inserts a new layer with the given name at the given index into the layers of this page.
+    ///
+    /// The new layer is drawn above the layers with lower indices and beneath the layers with the
+    /// same or higher indices.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the index is greater than the number of layers.
+    pub fn insert_layer(&mut self, idx: usize, name: impl Into<String>) {
         let layer = self.page.add_layer(name);
-        self.layers.push(Layer::new(layer, self.size));
+        // The layers are created in printpdf in the order of their creation.
+        let pdf_idx = self.layers.len();
+        let layer = Layer::new(layer, self.size, self.idx, pdf_idx, self.data.clone());
+        self.layers.insert(idx, layer);
     }
 
     /// Returns the number of layers on this page.
@@ -186,6 +374,36 @@ impl Page {
     pub fn last_layer(&self) -> &Layer {
         &self.layers[self.layers.len() - 1]
     }
+
+    /// Returns the layer with the given index in the order of creation.
+    fn find_layer(&self, pdf_idx: usize) -> Option<&Layer> {
+        self.layers.iter().find(|layer| layer.idx == pdf_idx)
+    }
+
+    /// Returns the index of the background layer of the layer with the given index in the order
+    /// of creation.  If the background layer does not exist yet, it is inserted directly beneath
+    /// the layer.
+    fn background_layer(&mut self, pdf_idx: usize) -> Option<usize> {
+        if let Some(background_idx) = self.backgrounds.get(&pdf_idx) {
+            return Some(*background_idx);
+        }
+        let idx = self.layers.iter().position(|layer| layer.idx == pdf_idx)?;
+        let background_idx = self.layers.len();
+        self.insert_layer(idx, "Background");
+        self.backgrounds.insert(pdf_idx, background_idx);
+        Some(background_idx)
+    }
+
+    /// Returns the indices of the layers in the order of creation sorted by the order in which
+    /// they are drawn, or `None` if the layers are drawn in the order of creation.
+    fn layer_order(&self) -> Option<Vec<usize>> {
+        let order: Vec<_> = self.layers.iter().map(|layer| layer.idx).collect();
+        if order.iter().enumerate().all(|(i, idx)| i == *idx) {
+            None
+        } else {
+            Some(order)
+        }
+    }
 }
 
 /// A layer of a page of a PDF document.
@@ -196,11 +414,27 @@ impl Page {
 pub struct Layer {
     layer: printpdf::PdfLayerReference,
     size: Size,
+    page_idx: usize,
+    // the index of the layer in the order of creation
+    idx: usize,
+    data: rc::Rc<cell::RefCell<SharedData>>,
 }
 
 impl Layer {
-    fn new(layer: printpdf::PdfLayerReference, size: Size) -> Layer {
-        Layer { layer, size }
+    fn new(
+        layer: printpdf::PdfLayerReference,
+        size: Size,
+        page_idx: usize,
+        idx: usize,
+        data: rc::Rc<cell::RefCell<SharedData>>,
+    ) -> Layer {
+        Layer {
+            layer,
+            size,
+            page_idx,
+            idx,
+            data,
+        }
     }
 
     /// Returns a drawable area for this layer.
@@ -214,6 +448,36 @@ impl Layer {
         position.y = self.size.height - position.y;
         position
     }
+
+    /// Saves the graphics state and applies the given transformation relative to the given
+    /// origin, relative to the upper left corner of the layer.
+    fn set_transform(&self, origin: Position, transform: Transform) {
+        // printpdf only supports rotations, scalings and translations, so we decompose the
+        // transformation into a rotation, a scaling and another rotation.
+        let (first_angle, scale_x, scale_y, second_angle) = transform.decompose();
+        let origin = self.transform_position(origin);
+        self.layer.save_graphics_state();
+        self.layer.set_ctm(printpdf::CurTransMat::Translate(
+            origin.x.into(),
+            origin.y.into(),
+        ));
+        if second_angle != 0.0 {
+            self.layer
+                .set_ctm(printpdf::CurTransMat::Rotate(second_angle));
+        }
+        if scale_x != 1.0 || scale_y != 1.0 {
+            self.layer
+                .set_ctm(printpdf::CurTransMat::Scale(scale_x, scale_y));
+        }
+        if first_angle != 0.0 {
+            self.layer
+                .set_ctm(printpdf::CurTransMat::Rotate(first_angle));
+        }
+        self.layer.set_ctm(printpdf::CurTransMat::Translate(
+            (Mm(0.0) - origin.x).into(),
+            (Mm(0.0) - origin.y).into(),
+        ));
+    }
 }
 
 /// A view on an area of a PDF layer that can be drawn on.
@@ -227,6 +491,10 @@ pub struct Area<'a> {
     layer: &'a Layer,
     origin: Position,
     size: Size,
+    // the origin and size of the area that footnotes are placed in, see enable_footnotes
+    footnote_region: Option<(Position, Size)>,
+    // the transformations and their origins relative to the layer, see transformed
+    transforms: Vec<(Position, Transform)>,
 }
 
 impl<'a> Area<'a> {
@@ -235,6 +503,8 @@ impl<'a> Area<'a> {
             layer,
             origin,
             size,
+            footnote_region: None,
+            transforms: Vec::new(),
         }
     }
 
@@ -248,8 +518,18 @@ impl<'a> Area<'a> {
     }
 
     /// Returns the size of this area.
+    ///
+    /// If footnotes are enabled for this area, the height is reduced so that the area does not
+    /// overlap with the space reserved for footnotes, see [`enable_footnotes`][].
+    ///
+    /// [`enable_footnotes`]: #method.enable_footnotes
     pub fn size(&self) -> Size {
-        self.size
+        let mut size = self.size;
+        if let Some((origin, region_size)) = self.footnote_region {
+            let bottom = origin.y + region_size.height - self.footnote_height();
+            size.height = size.height.min((bottom - self.origin.y).max(Mm(0.0)));
+        }
+        size
     }
 
     /// Adds the given offset to the area, reducing the drawable area.
@@ -297,11 +577,16 @@ impl<'a> Area<'a> {
         areas
     }
 
-    /// Draws a line with the given points and the given style.
+    /// Draws a line with the given points and the given line style.
     ///
-    /// Currently, this method only uses the color of the given style as the outline color (if set).
-    /// The points are relative to the upper left corner of the area.
-    pub fn draw_line(&self, points: Vec<Position>, style: Style) {
+    /// The points are relative to the upper left corner of the area.  The line style can also be
+    /// created from a [`Color`][] or a [`Style`][], using its color, stroke opacity and blend mode
+    /// for the line.
+    ///
+    /// [`Color`]: ../style/enum.Color.html
+    /// [`Style`]: ../style/struct.Style.html
+    pub fn draw_line(&self, points: Vec<Position>, line_style: impl Into<LineStyle>) {
+        let line_style = line_style.into();
         let line_points: Vec<_> = points
             .into_iter()
             .map(|pos| (self.transform_position(pos).into(), false))
@@ -313,15 +598,552 @@ impl<'a> Area<'a> {
             has_stroke: true,
             is_clipping_path: false,
         };
-        if let Some(color) = style.color() {
+        let transparency = Transparency::new(1.0, line_style.opacity(), line_style.blend_mode());
+        let is_saved = self.set_graphics_state(Some(&line_style), transparency);
+        if let Some(color) = line_style.color() {
             self.layer().set_outline_color(color.into());
         }
         self.layer().add_shape(line);
-        if style.color().is_some() {
+        if is_saved {
+            self.layer().restore_graphics_state();
+        } else if line_style.color().is_some() {
+            self.layer().set_outline_color(Color::Rgb(0, 0, 0).into());
+        }
+    }
+
+    /// Sets the thickness, dash pattern, cap and join style of the given line style and the given
+    /// transparency if they differ from the defaults.  In this case, the graphics state is saved
+    /// first and `true` is returned.  The caller then has to restore the graphics state after
+    /// drawing.
+    fn set_graphics_state(
+        &self,
+        line_style: Option<&LineStyle>,
+        transparency: Transparency,
+    ) -> bool {
+        let default = LineStyle::new();
+        let line_style = line_style.filter(|line_style| {
+            line_style.thickness() != default.thickness()
+                || !line_style.dash_pattern().is_empty()
+                || line_style.cap() != default.cap()
+                || line_style.join() != default.join()
+        });
+        let is_transparent = transparency != Transparency::default();
+        if line_style.is_none() && !is_transparent {
+            return false;
+        }
+
+        let layer = self.layer();
+        layer.save_graphics_state();
+        if is_transparent {
+            self.set_transparency(transparency);
+        }
+        if let Some(line_style) = line_style {
+            if line_style.thickness() != default.thickness() {
+                layer.set_outline_thickness(printpdf::Pt::from(line_style.thickness()).0);
+            }
+            if !line_style.dash_pattern().is_empty() {
+                let mut dashes = line_style
+                    .dash_pattern()
+                    .iter()
+                    .map(|length| (printpdf::Pt::from(*length).0.round() as i64).max(1));
+                layer.set_line_dash_pattern(printpdf::LineDashPattern::new(
+                    printpdf::Pt::from(line_style.dash_offset()).0.round() as i64,
+                    dashes.next(),
+                    dashes.next(),
+                    dashes.next(),
+                    dashes.next(),
+                    dashes.next(),
+                    dashes.next(),
+                ));
+            }
+            if line_style.cap() != default.cap() {
+                layer.set_line_cap_style(match line_style.cap() {
+                    LineCap::Butt => printpdf::LineCapStyle::Butt,
+                    LineCap::Round => printpdf::LineCapStyle::Round,
+                    LineCap::Square => printpdf::LineCapStyle::ProjectingSquare,
+                });
+            }
+            if line_style.join() != default.join() {
+                layer.set_line_join_style(match line_style.join() {
+                    LineJoin::Miter => printpdf::LineJoinStyle::Miter,
+                    LineJoin::Round => printpdf::LineJoinStyle::Round,
+                    LineJoin::Bevel => printpdf::LineJoinStyle::Limit,
+                });
+            }
+        }
+        true
+    }
+
+    /// Draws a rectangle with the given position and size and the given style.
+    ///
+    /// The position is the upper left corner of the rectangle, relative to the upper left corner
+    /// of the area.
+    pub fn draw_rectangle(&self, position: Position, size: impl Into<Size>, style: &ShapeStyle) {
+        let size = size.into();
+        self.draw_polygon(
+            vec![
+                position,
+                position + Position::new(size.width, 0),
+                position + Position::new(size.width, size.height),
+                position + Position::new(0, size.height),
+            ],
+            style,
+        );
+    }
+
+    /// Draws a rectangle with rounded corners with the given position, size and corner radius and
+    /// the given style.
+    ///
+    /// The position is the upper left corner of the rectangle, relative to the upper left corner
+    /// of the area.  The radius is limited to half of the width and the height of the rectangle.
+    pub fn draw_rounded_rectangle(
+        &self,
+        position: Position,
+        size: impl Into<Size>,
+        radius: impl Into<Mm>,
+        style: &ShapeStyle,
+    ) {
+        let size = size.into();
+        let radius = radius
+            .into()
+            .min(size.width / 2.0)
+            .min(size.height / 2.0)
+            .max(Mm(0.0));
+        let (left, top) = (position.x, position.y);
+        let (right, bottom) = (left + size.width, top + size.height);
+        let path = Path::new(Position::new(left + radius, top))
+            .line(Position::new(right - radius, top))
+            .arc(
+                Position::new(right, top),
+                Position::new(right, top + radius),
+            )
+            .line(Position::new(right, bottom - radius))
+            .arc(
+                Position::new(right, bottom),
+                Position::new(right - radius, bottom),
+            )
+            .line(Position::new(left + radius, bottom))
+            .arc(
+                Position::new(left, bottom),
+                Position::new(left, bottom - radius),
+            )
+            .line(Position::new(left, top + radius))
+            .arc(Position::new(left, top), Position::new(left + radius, top))
+            .closed();
+        self.draw_path(&path, style);
+    }
+
+    /// Draws an ellipse that fills the rectangle with the given position and size with the given
+    /// style.
+    ///
+    /// The position is the upper left corner of the bounding rectangle, relative to the upper left
+    /// corner of the area.  To draw a circle, use a square bounding rectangle.
+    pub fn draw_ellipse(&self, position: Position, size: impl Into<Size>, style: &ShapeStyle) {
+        let size = size.into();
+        let (left, top) = (position.x, position.y);
+        let (right, bottom) = (left + size.width, top + size.height);
+        let (x, y) = (left + size.width / 2.0, top + size.height / 2.0);
+        let path = Path::new(Position::new(right, y))
+            .arc(Position::new(right, bottom), Position::new(x, bottom))
+            .arc(Position::new(left, bottom), Position::new(left, y))
+            .arc(Position::new(left, top), Position::new(x, top))
+            .arc(Position::new(right, top), Position::new(right, y))
+            .closed();
+        self.draw_path(&path, style);
+    }
+
+    /// Draws a closed polygon with the given points and the given style.
+    ///
+    /// The points are relative to the upper left corner of the area.
+    pub fn draw_polygon(&self, points: Vec<Position>, style: &ShapeStyle) {
+        let mut points = points.into_iter();
+        if let Some(start) = points.next() {
+            let mut path = Path::new(start);
+            for point in points {
+                path.push_line(point);
+            }
+            path.set_closed();
+            self.draw_path(&path, style);
+        }
+    }
+
+    /// Draws the given path with the given style.
+    ///
+    /// The points of the path are relative to the upper left corner of the area.  If the style has
+    /// a fill color, the path is closed implicitly for filling it, but its outline is only closed
+    /// if the path is closed.
+    pub fn draw_path(&self, path: &Path, style: &ShapeStyle) {
+        if style.fill_color().is_none() && style.stroke_color().is_none() {
+            return;
+        }
+
+        // printpdf treats a point as the start of a Bézier curve if it and the next point are
+        // marked as control points.
+        let mut points = vec![(path.start, false)];
+        for segment in &path.segments {
+            match *segment {
+                PathSegment::Line(end) => points.push((end, false)),
+                PathSegment::Curve(c1, mut c2, end) => {
+                    if let Some(start) = points.last_mut() {
+                        start.1 = true;
+                    }
+                    // printpdf uses the wrong operator if both control points are equal, so we
+                    // move the second control point by a negligible distance.
+                    if c1 == c2 {
+                        c2.x += Mm(1e-6);
+                    }
+                    points.push((c1, true));
+                    points.push((c2, false));
+                    points.push((end, false));
+                }
+            }
+        }
+        let points: Vec<_> = points
+            .into_iter()
+            .map(|(pos, is_control)| (self.transform_position(pos).into(), is_control))
+            .collect();
+        let has_fill = style.fill_color().is_some();
+        let has_stroke = style.stroke_color().is_some();
+        let mut lines = Vec::new();
+        if has_fill && has_stroke && !path.is_closed {
+            // printpdf does not stroke filled paths unless they are closed, so we have to fill
+            // and stroke open paths separately.
+            lines.push(printpdf::Line {
+                points: points.clone(),
+                is_closed: true,
+                has_fill: true,
+                has_stroke: false,
+                is_clipping_path: false,
+            });
+            lines.push(printpdf::Line {
+                points,
+                is_closed: false,
+                has_fill: false,
+                has_stroke: true,
+                is_clipping_path: false,
+            });
+        } else {
+            lines.push(printpdf::Line {
+                points,
+                is_closed: path.is_closed || has_fill,
+                has_fill,
+                has_stroke,
+                is_clipping_path: false,
+            });
+        }
+
+        let fill_color = style
+            .fill_color()
+            .filter(|color| *color != Color::Rgb(0, 0, 0));
+        let stroke_color = style
+            .stroke_color()
+            .filter(|color| *color != Color::Rgb(0, 0, 0));
+        if let Some(color) = fill_color {
+            self.layer().set_fill_color(color.into());
+        }
+        if let Some(color) = stroke_color {
+            self.layer().set_outline_color(color.into());
+        }
+        let transparency = Transparency::new(
+            style.fill_opacity(),
+            style.line_style().map(LineStyle::opacity).unwrap_or(1.0),
+            style.blend_mode(),
+        );
+        let is_saved = self.set_graphics_state(style.line_style(), transparency);
+        for line in lines {
+            self.layer().add_shape(line);
+        }
+        if is_saved {
+            self.layer().restore_graphics_state();
+        }
+        if fill_color.is_some() {
+            self.layer().set_fill_color(Color::Rgb(0, 0, 0).into());
+        }
+        if stroke_color.is_some() {
             self.layer().set_outline_color(Color::Rgb(0, 0, 0).into());
         }
     }
 
+    /// Inserts the given image at the given position.
+    ///
+    /// *Only available if the `images` feature is enabled.*
+    ///
+    /// The position is the upper left corner of the image, relative to the upper left corner of
+    /// the area.  The size of the image is calculated from its pixel dimensions, the given
+    /// resolution in dots per inch and the given scale.  The image data must use one of the color
+    /// types supported by `printpdf`, i. e. greyscale or RGB without an alpha channel.
+    #[cfg(feature = "images")]
+    pub fn add_image(
+        &self,
+        image: &image::DynamicImage,
+        position: Position,
+        scale: crate::Scale,
+        dpi: f64,
+    ) {
+        use image::GenericImageView as _;
+
+        let height = Mm::from(f64::from(image.height()) * 25.4 / dpi * scale.y);
+        let position = self.transform_position(position + Position::new(0, height));
+        printpdf::Image::from_dynamic_image(image).add_to_layer(
+            self.layer().clone(),
+            Some(position.x.into()),
+            Some(position.y.into()),
+            None,
+            Some(scale.x),
+            Some(scale.y),
+            Some(dpi),
+        );
+    }
+
+    /// Calls the given callback with this area and applies the given transformation to
+    /// everything that it draws.
+    ///
+    /// The transformation is applied relative to the given origin, so the origin is not moved by
+    /// the transformation.  The origin is relative to the upper left corner of the area.  Callbacks
+    /// registered with [`defer`][] and [`defer_background`][] in the given callback are executed
+    /// with the same transformation.  Links and destinations are not transformed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use genpdf::{render, style};
+    /// fn draw_diamond(area: &render::Area<'_>) {
+    ///     let center = genpdf::Position::new(10, 10);
+    ///     area.transformed(center, render::Transform::rotate(45.0), |area| {
+    ///         area.draw_rectangle(
+    ///             genpdf::Position::new(5, 5),
+    ///             (10, 10),
+    ///             &style::ShapeStyle::new(),
+    ///         );
+    ///     });
+    /// }
+    /// ```
+    ///
+    /// [`defer`]: #method.defer
+    /// [`defer_background`]: #method.defer_background
+    pub fn transformed<T>(
+        &self,
+        origin: Position,
+        transform: Transform,
+        f: impl FnOnce(&Area<'a>) -> T,
+    ) -> T {
+        let origin = self.origin + origin;
+        self.layer.set_transform(origin, transform);
+        let mut area = self.clone();
+        area.transforms.push((origin, transform));
+        let result = f(&area);
+        self.layer().restore_graphics_state();
+        result
+    }
+
+    /// Returns the number of the page that contains this area, starting with 1.
+    pub fn page_number(&self) -> usize {
+        self.layer.page_idx + 1
+    }
+
+    /// Sets the size of the pages that are added after the page that contains this area.
+    ///
+    /// The size is used for all following pages until this method is called again.  It can be
+    /// retrieved with [`Renderer::take_next_page_size`][].
+    ///
+    /// [`Renderer::take_next_page_size`]: struct.Renderer.html#method.take_next_page_size
+    pub fn set_next_page_size(&self, size: impl Into<Size>) {
+        self.layer.data.borrow_mut().next_page_size = Some(size.into());
+    }
+
+    /// Registers a callback that is called with this area after all elements have been rendered.
+    ///
+    /// This can be used to draw content that depends on information that is only available after
+    /// the complete document has been laid out, for example page references.  The callbacks are
+    /// executed by [`Renderer::render_deferred`][].
+    ///
+    /// [`Renderer::render_deferred`]: struct.Renderer.html#method.render_deferred
+    pub fn defer<F>(&self, callback: F)
+    where
+        F: FnOnce(&fonts::FontCache, Area<'_>) -> Result<(), Error> + 'static,
+    {
+        let deferred = Deferred {
+            page_idx: self.layer.page_idx,
+            layer_idx: self.layer.idx,
+            origin: self.origin,
+            size: self.size(),
+            transforms: self.transforms.clone(),
+            callback: Box::new(callback),
+        };
+        self.layer.data.borrow_mut().deferred.push(deferred);
+    }
+
+    /// Registers a callback that draws the background of this area.
+    ///
+    /// The callback is called with an area with the same position and size on a background
+    /// layer that is drawn beneath the layer of this area, so that the background does not cover
+    /// the content of this area, regardless of the order in which they are drawn.  As the
+    /// background of an element is usually registered after the backgrounds of its children have
+    /// been registered, the callbacks are executed in reverse order:  Backgrounds that have been
+    /// registered later are drawn beneath the backgrounds that have been registered earlier.
+    ///
+    /// The callbacks are executed by [`Renderer::render_deferred`][].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use genpdf::{render, style};
+    /// fn fill(area: &render::Area<'_>, color: style::Color) {
+    ///     let size = area.size();
+    ///     area.defer_background(move |_, area| {
+    ///         let style = style::ShapeStyle::new()
+    ///             .with_fill_color(color)
+    ///             .with_stroke_color(None);
+    ///         area.draw_rectangle(genpdf::Position::default(), size, &style);
+    ///         Ok(())
+    ///     });
+    /// }
+    /// ```
+    ///
+    /// [`Renderer::render_deferred`]: struct.Renderer.html#method.render_deferred
+    pub fn defer_background<F>(&self, callback: F)
+    where
+        F: FnOnce(&fonts::FontCache, Area<'_>) -> Result<(), Error> + 'static,
+    {
+        let deferred = Deferred {
+            page_idx: self.layer.page_idx,
+            layer_idx: self.layer.idx,
+            origin: self.origin,
+            size: self.size(),
+            transforms: self.transforms.clone(),
+            callback: Box::new(callback),
+        };
+        self.layer.data.borrow_mut().backgrounds.push(deferred);
+    }
+
+    /// Enables footnotes for this area.
+    ///
+    /// Footnotes added with [`add_footnote`][] are placed at the bottom of this area.  The height
+    /// of this area and of all areas created from it is reduced by the height that is reserved
+    /// for the footnotes on the current page.  [`Document`][] enables footnotes for the area
+    /// returned by the page decorator.
+    ///
+    /// [`add_footnote`]: #method.add_footnote
+    /// [`Document`]: ../struct.Document.html
+    pub fn enable_footnotes(&mut self) {
+        self.footnote_region = Some((self.origin, self.size));
+    }
+
+    /// Returns the area that has been reserved for footnotes at the bottom of the area that
+    /// footnotes are enabled for, or `None` if footnotes are not enabled for this area.
+    pub fn footnote_area(&self) -> Option<Area<'a>> {
+        self.footnote_region.map(|(origin, size)| {
+            let height = self.footnote_height();
+            let origin = Position::new(origin.x, origin.y + size.height - height);
+            Area::new(self.layer, origin, Size::new(size.width, height))
+        })
+    }
+
+    /// Reserves space with the given height for the given footnote element at the bottom of the
+    /// area that footnotes are enabled for.
+    ///
+    /// The footnote elements of a page can be retrieved with [`take_footnotes`][] to render them
+    /// into the [`footnote_area`][].  If footnotes are not enabled for this area, the footnote is
+    /// ignored.
+    ///
+    /// [`take_footnotes`]: #method.take_footnotes
+    /// [`footnote_area`]: #method.footnote_area
+    pub fn add_footnote(&self, element: Box<dyn Element>, height: Mm) {
+        if self.footnote_region.is_some() {
+            let mut data = self.layer.data.borrow_mut();
+            let footnotes = data.footnotes.entry(self.layer.page_idx).or_default();
+            footnotes.height += height;
+            footnotes.elements.push(element);
+        }
+    }
+
+    /// Removes the footnote elements that have been added to the page that contains this area and
+    /// returns them.
+    ///
+    /// The space reserved for the footnotes is not changed.
+    pub fn take_footnotes(&self) -> Vec<Box<dyn Element>> {
+        self.layer
+            .data
+            .borrow_mut()
+            .footnotes
+            .get_mut(&self.layer.page_idx)
+            .map(|footnotes| mem::take(&mut footnotes.elements))
+            .unwrap_or_default()
+    }
+
+    /// Returns the next number for automatically numbered footnotes, starting with 1.
+    pub fn next_footnote_number(&self) -> usize {
+        let mut data = self.layer.data.borrow_mut();
+        data.footnote_count += 1;
+        data.footnote_count
+    }
+
+    /// Returns the height reserved for footnotes on the page that contains this area.
+    fn footnote_height(&self) -> Mm {
+        self.layer
+            .data
+            .borrow()
+            .footnotes
+            .get(&self.layer.page_idx)
+            .map(|footnotes| footnotes.height)
+            .unwrap_or_default()
+    }
+
+    /// Adds an entry with the given title and level to the document outline that points to the
+    /// upper left corner of this area.
+    ///
+    /// The outline, also known as bookmarks, is displayed by PDF viewers for navigating the
+    /// document.  The entries are nested according to their level:  An entry is a child of the
+    /// last preceding entry with a lower level.
+    pub fn add_outline_entry(&self, title: impl Into<String>, level: usize) {
+        self.layer
+            .data
+            .borrow_mut()
+            .document
+            .add_outline_entry(postprocess::OutlineEntry {
+                title: title.into(),
+                level,
+                page: self.layer.page_idx,
+                position: self.origin,
+            });
+    }
+
+    /// Adds a named destination with the given name that points to the upper left corner of this
+    /// area.
+    ///
+    /// Named destinations can be used as link targets, see [`Link::Destination`][].  If there
+    /// already is a destination with the same name, it is not changed.
+    ///
+    /// [`Link::Destination`]: ../style/enum.Link.html#variant.Destination
+    pub fn add_destination(&self, name: impl Into<String>) {
+        self.layer.data.borrow_mut().document.add_destination(
+            name.into(),
+            self.layer.page_idx,
+            self.origin,
+        );
+    }
+
+    /// Adds a link annotation with the given target that covers the rectangle with the given
+    /// position and size.
+    ///
+    /// The position is relative to the upper left corner of the area.  If the link target is a
+    /// named destination, it must be added to the document with [`add_destination`][] before the
+    /// document is written.
+    ///
+    /// [`add_destination`]: #method.add_destination
+    pub fn add_link(&self, position: Position, size: impl Into<Size>, link: Link) {
+        self.layer
+            .data
+            .borrow_mut()
+            .document
+            .add_link(postprocess::LinkAnnotation {
+                link,
+                page: self.layer.page_idx,
+                position: self.origin + position,
+                size: size.into(),
+            });
+    }
+
     /// Tries to draw the given string at the given position and returns `true` if the area was
     /// large enough to draw the string.
     ///
@@ -348,6 +1170,7 @@ impl<'a> Area<'a> {
     /// The given style is only used to calculate the line height of the section.  The position is
     /// relative to the upper left corner of the area.  The font cache must contain the PDF font
     /// for all fonts printed with the text section.
+    #[allow(clippy::result_unit_err)]
     pub fn text_section<'f>(
         &self,
         font_cache: &'f fonts::FontCache,
@@ -364,18 +1187,371 @@ impl<'a> Area<'a> {
         self.layer.transform_position(position)
     }
 
+    /// Sets the opacities and the blend mode of the given transparency.
+    ///
+    /// This adds a new graphics state to the page.  As graphics states cannot be saved in text
+    /// sections, they have to be reset manually.
+    fn set_transparency(&self, transparency: Transparency) {
+        self.layer().set_blend_mode(transparency.blend_mode.into());
+        self.layer.data.borrow_mut().document.add_opacity(
+            self.layer.page_idx,
+            postprocess::Opacity {
+                fill: transparency.fill_opacity,
+                stroke: transparency.stroke_opacity,
+            },
+        );
+    }
+
     fn layer(&self) -> &printpdf::PdfLayerReference {
         &self.layer.layer
     }
 }
 
+/// The opacities and the blend mode used for drawing.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Transparency {
+    fill_opacity: f64,
+    stroke_opacity: f64,
+    blend_mode: BlendMode,
+}
+
+impl Transparency {
+    fn new(fill_opacity: f64, stroke_opacity: f64, blend_mode: BlendMode) -> Transparency {
+        Transparency {
+            fill_opacity,
+            stroke_opacity,
+            blend_mode,
+        }
+    }
+}
+
+impl Default for Transparency {
+    fn default() -> Transparency {
+        Transparency::new(1.0, 1.0, BlendMode::Normal)
+    }
+}
+
+impl From<Style> for Transparency {
+    fn from(style: Style) -> Transparency {
+        Transparency::new(
+            style.fill_opacity(),
+            style.stroke_opacity(),
+            style.blend_mode(),
+        )
+    }
+}
+
+/// The distance of the control points of a cubic Bézier curve that approximates a quarter circle
+/// with radius 1 from the start and the end point.
+const KAPPA: f64 = 0.552_284_749_831;
+
+/// A path that consists of straight lines and cubic Bézier curves.
+///
+/// Paths can be drawn with [`Area::draw_path`][] or with the [`Shape`][] element.
+///
+/// # Example
+///
+/// ```
+/// use genpdf::{render, Position};
+/// let arrow = render::Path::new(Position::new(0, 5))
+///     .line(Position::new(15, 5))
+///     .curve(Position::new(18, 5), Position::new(20, 3), Position::new(20, 0))
+///     .closed();
+/// ```
+///
+/// [`Area::draw_path`]: struct.Area.html#method.draw_path
+/// [`Shape`]: ../elements/struct.Shape.html
+#[derive(Clone, Debug, PartialEq)]
+pub struct Path {
+    start: Position,
+    segments: Vec<PathSegment>,
+    is_closed: bool,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum PathSegment {
+    Line(Position),
+    // the control points and the end point
+    Curve(Position, Position, Position),
+}
+
+impl Path {
+    /// Creates a new path with the given start point.
+    pub fn new(start: impl Into<Position>) -> Path {
+        Path {
+            start: start.into(),
+            segments: Vec::new(),
+            is_closed: false,
+        }
+    }
+
+    /// Adds a straight line from the current point to the given end point.
+    pub fn push_line(&mut self, end: impl Into<Position>) {
+        self.segments.push(PathSegment::Line(end.into()));
+    }
+
+    /// Adds a straight line from the current point to the given end point and returns the path.
+    pub fn line(mut self, end: impl Into<Position>) -> Path {
+        self.push_line(end);
+        self
+    }
+
+    /// Adds an arc that approximates a quarter ellipse from the current point to the given end
+    /// point.
+    ///
+    /// The corner is the point where the tangents of the arc at the current point and at the end
+    /// point meet, for example the corner of a rectangle with rounded corners.
+    pub fn push_arc(&mut self, corner: impl Into<Position>, end: impl Into<Position>) {
+        let start = self.current_point();
+        let (corner, end) = (corner.into(), end.into());
+        let interpolate = |from: Position, to: Position| {
+            Position::new(
+                from.x + (to.x - from.x) * KAPPA,
+                from.y + (to.y - from.y) * KAPPA,
+            )
+        };
+        self.push_curve(interpolate(start, corner), interpolate(end, corner), end);
+    }
+
+    /// Adds an arc that approximates a quarter ellipse from the current point to the given end
+    /// point and returns the path.
+    ///
+    /// See [`push_arc`][] for more information.
+    ///
+    /// [`push_arc`]: #method.push_arc
+    pub fn arc(mut self, corner: impl Into<Position>, end: impl Into<Position>) -> Path {
+        self.push_arc(corner, end);
+        self
+    }
+
+    /// Adds a cubic Bézier curve with the given control points from the current point to the
+    /// given end point.
+    pub fn push_curve(
+        &mut self,
+        control1: impl Into<Position>,
+        control2: impl Into<Position>,
+        end: impl Into<Position>,
+    ) {
+        self.segments.push(PathSegment::Curve(
+            control1.into(),
+            control2.into(),
+            end.into(),
+        ));
+    }
+
+    /// Adds a cubic Bézier curve with the given control points from the current point to the
+    /// given end point and returns the path.
+    pub fn curve(
+        mut self,
+        control1: impl Into<Position>,
+        control2: impl Into<Position>,
+        end: impl Into<Position>,
+    ) -> Path {
+        self.push_curve(control1, control2, end);
+        self
+    }
+
+    /// Closes this path with a straight line from the current point to the start point.
+    pub fn set_closed(&mut self) {
+        self.is_closed = true;
+    }
+
+    /// Closes this path with a straight line from the current point to the start point and
+    /// returns the path.
+    pub fn closed(mut self) -> Path {
+        self.set_closed();
+        self
+    }
+
+    /// Returns whether this path is closed.
+    pub fn is_closed(&self) -> bool {
+        self.is_closed
+    }
+
+    /// Returns the end point of the last segment of this path, or the start point if the path
+    /// is empty.
+    pub fn current_point(&self) -> Position {
+        match self.segments.last() {
+            Some(PathSegment::Line(end)) | Some(PathSegment::Curve(_, _, end)) => *end,
+            None => self.start,
+        }
+    }
+
+    /// Returns all points of this path, including the control points of Bézier curves.
+    pub fn points(&self) -> Vec<Position> {
+        let mut points = vec![self.start];
+        for segment in &self.segments {
+            match *segment {
+                PathSegment::Line(end) => points.push(end),
+                PathSegment::Curve(c1, c2, end) => points.extend_from_slice(&[c1, c2, end]),
+            }
+        }
+        points
+    }
+}
+
+/// An affine transformation that rotates, scales and skews content.
+///
+/// Transformations can be applied to the content drawn on an [`Area`][] with
+/// [`Area::transformed`][] or to an element with the [`Transformed`][] element.  Transformations
+/// can be combined with [`then`][].
+///
+/// # Example
+///
+/// ```
+/// use genpdf::render::Transform;
+/// let transform = Transform::scale(0.5, 0.5).then(Transform::rotate(90.0));
+/// ```
+///
+/// [`Area`]: struct.Area.html
+/// [`Area::transformed`]: struct.Area.html#method.transformed
+/// [`Transformed`]: ../elements/struct.Transformed.html
+/// [`then`]: #method.then
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Transform {
+    // the transformation matrix in PDF coordinates (with the y axis pointing up):
+    // x' = a * x + c * y, y' = b * x + d * y
+    a: f64,
+    b: f64,
+    c: f64,
+    d: f64,
+}
+
+impl Transform {
+    /// Returns the identity transformation that does not change the content.
+    pub fn identity() -> Transform {
+        Transform::new(1.0, 0.0, 0.0, 1.0)
+    }
+
+    /// Returns a transformation that rotates the content counter-clockwise by the given angle in
+    /// degrees.
+    pub fn rotate(angle: f64) -> Transform {
+        let (sin, cos) = angle.to_radians().sin_cos();
+        Transform::new(cos, sin, -sin, cos)
+    }
+
+    /// Returns a transformation that scales the content horizontally and vertically by the given
+    /// factors.
+    pub fn scale(x: f64, y: f64) -> Transform {
+        Transform::new(x, 0.0, 0.0, y)
+    }
+
+    /// Returns a transformation that skews the content by the given angles in degrees.
+    ///
+    /// A positive horizontal angle slants vertical lines to the right, like italic text.  A
+    /// positive vertical angle slants horizontal lines upwards.
+    pub fn skew(x: f64, y: f64) -> Transform {
+        Transform::new(1.0, y.to_radians().tan(), x.to_radians().tan(), 1.0)
+    }
+
+    fn new(a: f64, b: f64, c: f64, d: f64) -> Transform {
+        Transform { a, b, c, d }
+    }
+
+    /// Returns a transformation that applies this transformation and then the given
+    /// transformation.
+    pub fn then(self, other: Transform) -> Transform {
+        Transform::new(
+            other.a * self.a + other.c * self.b,
+            other.b * self.a + other.d * self.b,
+            other.a * self.c + other.c * self.d,
+            other.b * self.c + other.d * self.d,
+        )
+    }
+
+    /// Applies this transformation to the given position, relative to the origin of the
+    /// transformation.
+    pub fn apply(&self, position: Position) -> Position {
+        let (x, y) = (position.x.0, position.y.0);
+        // Positions use a y axis that points down, so we have to flip it.
+        Position::new(self.a * x - self.c * y, self.d * y - self.b * x)
+    }
+
+    /// Returns the size of the bounding box of a rectangle with the given size after applying
+    /// this transformation.
+    pub fn bounding_size(&self, size: Size) -> Size {
+        let corners = [
+            Position::new(size.width, 0),
+            Position::new(0, size.height),
+            Position::new(size.width, size.height),
+        ];
+        let (mut min, mut max) = (Position::default(), Position::default());
+        for corner in corners.iter() {
+            let corner = self.apply(*corner);
+            min = Position::new(min.x.min(corner.x), min.y.min(corner.y));
+            max = Position::new(max.x.max(corner.x), max.y.max(corner.y));
+        }
+        Size::new(max.x - min.x, max.y - min.y)
+    }
+
+    /// Decomposes this transformation into a rotation, a scaling and another rotation.  Returns
+    /// the first angle in degrees, the scaling factors and the second angle in degrees.
+    fn decompose(&self) -> (f64, f64, f64, f64) {
+        let Transform { a, b, c, d } = *self;
+        if b.abs() < 1e-9 && c.abs() < 1e-9 {
+            return (0.0, a, d, 0.0);
+        }
+        // see the closed-form singular value decomposition of 2x2 matrices
+        let (e, f, g, h) = ((a + d) / 2.0, (a - d) / 2.0, (b + c) / 2.0, (b - c) / 2.0);
+        let (q, r) = (e.hypot(h), f.hypot(g));
+        let angle = h.atan2(e);
+        let (first, second) = if r < 1e-9 {
+            // The scaling is uniform, so we only need one rotation.
+            (0.0, angle)
+        } else {
+            let angle2 = g.atan2(f);
+            ((angle - angle2) / 2.0, (angle + angle2) / 2.0)
+        };
+        let snap = |angle: f64| {
+            let degrees = angle.to_degrees();
+            if degrees.abs() < 1e-9 {
+                0.0
+            } else {
+                degrees
+            }
+        };
+        (snap(first), q + r, q - r, snap(second))
+    }
+}
+
+impl Default for Transform {
+    fn default() -> Transform {
+        Transform::identity()
+    }
+}
+
 /// A text section that is drawn on an area of a PDF layer.
 pub struct TextSection<'a, 'f, 'l> {
     font_cache: &'f fonts::FontCache,
     area: &'a Area<'l>,
     line_height: Mm,
+    // the vertical offset of the baseline from the top of the line
+    baseline: Mm,
     cursor: Position,
     fill_color: Option<Color>,
+    // the offset of the text from the baseline for superscripts and subscripts
+    rise: Mm,
+    word_spacing: Mm,
+    // the horizontal offset of the current position from the start of the line
+    x: Mm,
+    // the horizontal offset of the line start of the text object from the start of the line
+    line_offset: Mm,
+    // the current link target and the horizontal offsets of its start and end
+    link: Option<(Link, Mm, Mm)>,
+    // the current decoration lines and the horizontal offsets of their start and end
+    decorations: Vec<(DecorationLine, Mm, Mm)>,
+    // the finished decoration lines that are drawn after the text section has been closed
+    decoration_rects: Vec<(Position, Size, ShapeStyle)>,
+    // the current opacities and blend mode
+    transparency: Transparency,
+}
+
+/// A line that decorates text, for example an underline.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct DecorationLine {
+    metrics: fonts::DecorationMetrics,
+    color: Option<Color>,
+    transparency: Transparency,
 }
 
 impl<'a, 'f, 'l> TextSection<'a, 'f, 'l> {
@@ -387,7 +1563,9 @@ impl<'a, 'f, 'l> TextSection<'a, 'f, 'l> {
     ) -> Result<TextSection<'a, 'f, 'l>, ()> {
         let height = style.font(font_cache).glyph_height(style.font_size());
 
-        if position.y + height > area.size.height {
+        // We allow a small tolerance for rounding errors, for example if the area height has
+        // been calculated from the measured height of an element.
+        if position.y + height > area.size().height + Mm(1e-6) {
             return Err(());
         }
 
@@ -396,8 +1574,17 @@ impl<'a, 'f, 'l> TextSection<'a, 'f, 'l> {
             font_cache,
             area,
             line_height,
+            baseline: height,
             cursor: position,
             fill_color: None,
+            rise: Mm(0.0),
+            word_spacing: Mm(0.0),
+            x: Mm(0.0),
+            line_offset: Mm(0.0),
+            link: None,
+            decorations: Vec::new(),
+            decoration_rects: Vec::new(),
+            transparency: Transparency::default(),
         };
         section.layer().begin_text_section();
         section.layer().set_line_height(line_height.0);
@@ -412,18 +1599,122 @@ impl<'a, 'f, 'l> TextSection<'a, 'f, 'l> {
     /// line.
     #[must_use]
     pub fn add_newline(&mut self) -> bool {
-        if self.cursor.y + self.line_height > self.area.size.height {
+        if self.cursor.y + self.line_height > self.area.size().height {
             false
         } else {
+            if self.line_offset != Mm(0.0) {
+                self.layer()
+                    .set_text_cursor((Mm(0.0) - self.line_offset).into(), Mm(0.0).into());
+            }
+            self.finish_link();
+            self.finish_decorations();
             self.layer().add_line_break();
             self.cursor.y += self.line_height;
+            self.x = Mm(0.0);
+            self.line_offset = Mm(0.0);
             true
         }
     }
 
+    /// Sets the additional space that is inserted after every space character.
+    ///
+    /// This can be used to justify text.  The spacing is applied to all strings printed after
+    /// calling this method.
+    pub fn set_word_spacing(&mut self, spacing: Mm) {
+        self.word_spacing = spacing;
+    }
+
+    /// Sets the link target for all strings printed after calling this method.
+    ///
+    /// The link annotation covers the printed text, excluding trailing spaces.  Consecutive
+    /// strings on the same line with the same link target share one link annotation.
+    pub fn set_link(&mut self, link: Option<&Link>) {
+        if self.link.as_ref().map(|(l, _, _)| l) != link {
+            self.finish_link();
+            self.link = link.map(|link| (link.clone(), self.x, self.x));
+        }
+    }
+
+    /// Adds the link annotation for the current link, if set, and resets its start position.
+    fn finish_link(&mut self) {
+        if let Some((link, start, end)) = &mut self.link {
+            if *end > *start {
+                let height = self.line_height;
+                self.area.add_link(
+                    Position::new(self.cursor.x + *start, self.cursor.y),
+                    Size::new(*end - *start, height),
+                    link.clone(),
+                );
+            }
+            *start = Mm(0.0);
+            *end = Mm(0.0);
+        }
+    }
+
+    /// Stores the rectangles for the current decoration lines, if any, and resets their start
+    /// positions.
+    fn finish_decorations(&mut self) {
+        for (line, start, end) in &mut self.decorations {
+            if *end > *start {
+                let top = self.cursor.y + self.baseline
+                    - line.metrics.position
+                    - line.metrics.thickness / 2.0;
+                let style = ShapeStyle::new()
+                    .with_fill_color(line.color.unwrap_or(Color::Rgb(0, 0, 0)))
+                    .with_fill_opacity(line.transparency.fill_opacity)
+                    .with_blend_mode(line.transparency.blend_mode)
+                    .with_stroke_color(None);
+                self.decoration_rects.push((
+                    Position::new(self.cursor.x + *start, top),
+                    Size::new(*end - *start, line.metrics.thickness),
+                    style,
+                ));
+            }
+            *start = Mm(0.0);
+            *end = Mm(0.0);
+        }
+    }
+
+    /// Sets the decoration lines for the given style.  Consecutive strings on the same line with
+    /// the same decoration lines share them.
+    ///
+    /// The font size and the baseline offset are the values used to render the text, see
+    /// `Style::script_metrics`.
+    fn set_decorations(&mut self, style: Style, font_size: u8, rise: Mm) {
+        let font = style.font(self.font_cache);
+        let mut metrics = Vec::new();
+        if style.is_underline() {
+            metrics.push(font.underline_metrics(font_size));
+        }
+        if style.is_strikethrough() {
+            metrics.push(font.strikethrough_metrics(font_size));
+        }
+        if style.is_overline() {
+            metrics.push(font.overline_metrics(font_size));
+        }
+        let lines: Vec<_> = metrics
+            .into_iter()
+            .map(|metrics| DecorationLine {
+                metrics: fonts::DecorationMetrics::new(metrics.position + rise, metrics.thickness),
+                color: style.color(),
+                transparency: style.into(),
+            })
+            .collect();
+        if !self.decorations.iter().map(|(line, _, _)| line).eq(&lines) {
+            self.finish_decorations();
+            self.decorations = lines
+                .into_iter()
+                .map(|line| (line, self.x, self.x))
+                .collect();
+        }
+    }
+
     /// Prints the given string with the given style.
     ///
-    /// The font cache for this text section must contain the PDF font for the given style.
+    /// The text is drawn with the fill opacity and the blend mode of the style.  If the style has
+    /// the underline, strikethrough or overline effect, the decoration lines are drawn after the
+    /// text section has been closed.  The font cache for this text section must
+    /// contain the PDF font for the given style.
     pub fn print_str(&mut self, s: impl AsRef<str>, style: Style) -> Result<(), Error> {
         let font = style.font(self.font_cache);
         if font.is_builtin() {
@@ -432,6 +1723,11 @@ impl<'a, 'f, 'l> TextSection<'a, 'f, 'l> {
             // characters.
             ensure_win1252_encoding(s.as_ref())?;
         }
+        let (font_size, rise) = style
+            .script_metrics(self.font_cache)
+            .map(|metrics| (metrics.font_size, metrics.offset))
+            .unwrap_or_else(|| (style.font_size(), Mm(0.0)));
+        self.set_decorations(style, font_size, rise);
 
         let font = self
             .font_cache
@@ -443,8 +1739,59 @@ impl<'a, 'f, 'l> TextSection<'a, 'f, 'l> {
             self.layer().set_fill_color(Color::Rgb(0, 0, 0).into());
         }
         self.fill_color = style.color();
-        self.layer().set_font(font, style.font_size().into());
-        self.layer().write_text(s.as_ref(), font);
+        let transparency = Transparency::from(style);
+        if transparency != self.transparency {
+            self.area.set_transparency(transparency);
+            self.transparency = transparency;
+        }
+        self.layer().set_font(font, font_size.into());
+        if rise != self.rise {
+            self.layer().set_line_offset(printpdf::Pt::from(rise).0);
+            self.rise = rise;
+        }
+
+        if self.word_spacing == Mm(0.0) {
+            self.layer().write_text(s.as_ref(), font);
+            self.x += style.str_width(self.font_cache, s.as_ref());
+        } else {
+            // PDF word spacing is only applied to single-byte encodings, so we have to position
+            // the words manually.
+            let mut s = s.as_ref();
+            while !s.is_empty() {
+                let n = s.find(' ').map(|i| i + 1).unwrap_or_else(|| s.len());
+                let (word, rest) = s.split_at(n);
+                s = rest;
+                self.layer().write_text(word, font);
+                self.x += style.str_width(self.font_cache, word);
+                if word.ends_with(' ') {
+                    self.x += self.word_spacing;
+                    self.layer()
+                        .set_text_cursor((self.x - self.line_offset).into(), Mm(0.0).into());
+                    self.line_offset = self.x;
+                }
+            }
+        }
+
+        // Links and decoration lines cover the printed text, excluding trailing spaces.
+        let trimmed = s.as_ref().trim_end();
+        let text_end = if trimmed.is_empty() {
+            None
+        } else {
+            let trailing = style.str_width(self.font_cache, &s.as_ref()[trimmed.len()..]);
+            let spacing = if trimmed.len() < s.as_ref().len() {
+                self.word_spacing
+            } else {
+                Mm(0.0)
+            };
+            Some(self.x - trailing - spacing)
+        };
+        let x = self.x;
+        if let Some((_, start, end)) = &mut self.link {
+            extend_range(start, end, x, text_end);
+        }
+        for (_, start, end) in &mut self.decorations {
+            extend_range(start, end, x, text_end);
+        }
         Ok(())
     }
 
@@ -455,10 +1802,36 @@ impl<'a, 'f, 'l> TextSection<'a, 'f, 'l> {
 
 impl<'a, 'f, 'l> Drop for TextSection<'a, 'f, 'l> {
     fn drop(&mut self) {
+        self.finish_link();
+        self.finish_decorations();
         if self.fill_color.is_some() {
             self.layer().set_fill_color(Color::Rgb(0, 0, 0).into());
         }
+        // The text rise is part of the graphics state, so we have to reset it.
+        if self.rise != Mm(0.0) {
+            self.layer().set_line_offset(0.0);
+        }
+        // The graphics state cannot be saved and restored in a text section, so we have to reset
+        // the transparency manually.
+        if self.transparency != Transparency::default() {
+            self.area.set_transparency(Transparency::default());
+        }
         self.layer().end_text_section();
+        for (position, size, style) in self.decoration_rects.drain(..) {
+            self.area.draw_rectangle(position, size, &style);
+        }
+    }
+}
+
+/// Extends the range with the given start and end to the given text end.  If the text end is not
+/// set because only whitespace has been printed and the range is still empty, the range is moved
+/// to the current position instead.
+fn extend_range(start: &mut Mm, end: &mut Mm, x: Mm, text_end: Option<Mm>) {
+    if let Some(text_end) = text_end {
+        *end = text_end;
+    } else if *end == *start {
+        *start = x;
+        *end = x;
     }
 }
 
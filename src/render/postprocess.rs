@@ -0,0 +1,416 @@
+// SPDX-FileCopyrightText: 2020 Robin Krahl <robin.krahl@ireas.org>
+// SPDX-License-Identifier: Apache-2.0 or MIT
+
+//! Post-processing of the PDF document generated by `printpdf`.
+//!
+//! Some PDF features are not supported by `printpdf`.  We collect the required data during the
+//! rendering process and add it to the `lopdf` document after `printpdf` generated it.
+
+use std::collections;
+
+use lopdf::content::{Content, Operation};
+use lopdf::{Dictionary, Object, ObjectId, StringFormat};
+
+use crate::error::{Error, ErrorKind};
+use crate::style::Link;
+use crate::{Position, Size};
+
+/// Data that has been collected during the rendering process and that has to be added to the
+/// generated document.
+#[derive(Clone, Debug, Default)]
+pub struct DocumentData {
+    outline: Vec<OutlineEntry>,
+    destinations: collections::HashMap<String, (usize, Position)>,
+    links: Vec<LinkAnnotation>,
+    layer_orders: collections::HashMap<usize, Vec<usize>>,
+    opacities: collections::HashMap<usize, Vec<Opacity>>,
+}
+
+impl DocumentData {
+    pub fn is_empty(&self) -> bool {
+        self.outline.is_empty()
+            && self.destinations.is_empty()
+            && self.links.is_empty()
+            && self.layer_orders.is_empty()
+            && self.opacities.is_empty()
+    }
+
+    pub fn add_outline_entry(&mut self, entry: OutlineEntry) {
+        self.outline.push(entry);
+    }
+
+    /// Adds a named destination.  If there already is a destination with the same name, it is
+    /// not changed.
+    pub fn add_destination(&mut self, name: String, page: usize, position: Position) {
+        self.destinations.entry(name).or_insert((page, position));
+    }
+
+    pub fn add_link(&mut self, link: LinkAnnotation) {
+        self.links.push(link);
+    }
+
+    /// Sets the order in which the layers of a page are drawn.  The order contains the indices of
+    /// the layers in the order of creation.
+    pub fn set_layer_order(&mut self, page: usize, order: Vec<usize>) {
+        self.layer_orders.insert(page, order);
+    }
+
+    /// Adds the opacity for the next graphics state that is added to the given page.
+    ///
+    /// `printpdf` only supports graphics states with a blend mode, so the opacity is added to the
+    /// graphics state after the document has been generated.  This method must be called exactly
+    /// once for every graphics state that is added to a page.
+    pub fn add_opacity(&mut self, page: usize, opacity: Opacity) {
+        self.opacities.entry(page).or_default().push(opacity);
+    }
+
+    /// Adds the collected data to the given document.
+    ///
+    /// The page sizes are used to transform the positions that are relative to the upper left
+    /// corner of the page to PDF coordinates.
+    pub fn apply(&self, doc: &mut lopdf::Document, page_sizes: &[Size]) -> Result<(), Error> {
+        let page_ids: Vec<ObjectId> = doc.get_pages().values().copied().collect();
+        if page_ids.len() != page_sizes.len() {
+            return Err(Error::new(
+                "The number of pages in the generated document does not match",
+                ErrorKind::Internal,
+            ));
+        }
+        let dests: Vec<_> = page_ids
+            .into_iter()
+            .zip(page_sizes)
+            .map(|(id, size)| Destination::new(id, *size))
+            .collect();
+
+        if !self.outline.is_empty() {
+            let outline_id = add_outline(doc, &self.outline, &dests);
+            let catalog = get_catalog(doc)?;
+            catalog.set("Outlines", Object::Reference(outline_id));
+            catalog.set("PageMode", Object::Name(b"UseOutlines".to_vec()));
+        }
+
+        for (idx, dest) in dests.iter().enumerate() {
+            if let Some(order) = self.layer_orders.get(&idx) {
+                reorder_layers(doc, dest.page_id, order)?;
+            }
+            if let Some(opacities) = self.opacities.get(&idx) {
+                set_opacities(doc, dest.page_id, opacities)?;
+            }
+
+            let mut annots = Vec::new();
+            for link in self.links.iter().filter(|link| link.page == idx) {
+                let annot = self.create_link_annotation(link, dest, &dests)?;
+                annots.push(Object::Reference(doc.add_object(annot)));
+            }
+            if !annots.is_empty() {
+                add_annotations(doc, dest.page_id, annots)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn create_link_annotation(
+        &self,
+        link: &LinkAnnotation,
+        dest: &Destination,
+        dests: &[Destination],
+    ) -> Result<Dictionary, Error> {
+        let mut annot = Dictionary::new();
+        annot.set("Type", Object::Name(b"Annot".to_vec()));
+        annot.set("Subtype", Object::Name(b"Link".to_vec()));
+        annot.set("Rect", dest.to_rect(link.position, link.size));
+        annot.set("Border", Object::Array(vec![0.into(), 0.into(), 0.into()]));
+        match &link.link {
+            Link::Uri(uri) => {
+                let mut action = Dictionary::new();
+                action.set("S", Object::Name(b"URI".to_vec()));
+                action.set(
+                    "URI",
+                    Object::String(uri.as_bytes().to_vec(), StringFormat::Literal),
+                );
+                annot.set("A", action);
+            }
+            Link::Destination(name) => {
+                let (page, position) = self.destinations.get(name).ok_or_else(|| {
+                    Error::new(
+                        format!("Could not find the link destination {}", name),
+                        ErrorKind::InvalidData,
+                    )
+                })?;
+                annot.set("Dest", dests[*page].to_object(*position));
+            }
+        }
+        Ok(annot)
+    }
+}
+
+/// An entry in the document outline.
+#[derive(Clone, Debug)]
+pub struct OutlineEntry {
+    pub title: String,
+    pub level: usize,
+    pub page: usize,
+    pub position: Position,
+}
+
+/// A link annotation that covers a rectangle on a page.
+#[derive(Clone, Debug)]
+pub struct LinkAnnotation {
+    pub link: Link,
+    pub page: usize,
+    pub position: Position,
+    pub size: Size,
+}
+
+/// The fill and stroke opacity of a graphics state.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Opacity {
+    pub fill: f64,
+    pub stroke: f64,
+}
+
+/// A page of the document that can be used as a destination for outline entries and links.
+struct Destination {
+    page_id: ObjectId,
+    page_size: Size,
+}
+
+impl Destination {
+    fn new(page_id: ObjectId, page_size: Size) -> Destination {
+        Destination { page_id, page_size }
+    }
+
+    /// Returns a destination array that points to the given position on this page.
+    fn to_object(&self, position: Position) -> Object {
+        let left: printpdf::Pt = position.x.into();
+        let top: printpdf::Pt = (self.page_size.height - position.y).into();
+        Object::Array(vec![
+            Object::Reference(self.page_id),
+            Object::Name(b"XYZ".to_vec()),
+            Object::Real(left.0),
+            Object::Real(top.0),
+            Object::Null,
+        ])
+    }
+
+    /// Returns a rectangle array for the rectangle on this page with the given upper left corner
+    /// and size.
+    fn to_rect(&self, position: Position, size: Size) -> Object {
+        let left: printpdf::Pt = position.x.into();
+        let right: printpdf::Pt = (position.x + size.width).into();
+        let top: printpdf::Pt = (self.page_size.height - position.y).into();
+        let bottom: printpdf::Pt = (self.page_size.height - position.y - size.height).into();
+        Object::Array(vec![
+            Object::Real(left.0),
+            Object::Real(bottom.0),
+            Object::Real(right.0),
+            Object::Real(top.0),
+        ])
+    }
+}
+
+/// Adds the given outline entries to the document and returns the ID of the outline dictionary.
+fn add_outline(
+    doc: &mut lopdf::Document,
+    entries: &[OutlineEntry],
+    dests: &[Destination],
+) -> ObjectId {
+    let outline_id = doc.new_object_id();
+    let ids: Vec<ObjectId> = entries.iter().map(|_| doc.new_object_id()).collect();
+
+    // An entry is the child of the last preceding entry with a lower level.
+    let mut parents = Vec::with_capacity(entries.len());
+    let mut stack: Vec<usize> = Vec::new();
+    for (i, entry) in entries.iter().enumerate() {
+        while stack
+            .last()
+            .map(|j| entries[*j].level >= entry.level)
+            .unwrap_or_default()
+        {
+            stack.pop();
+        }
+        parents.push(stack.last().copied());
+        stack.push(i);
+    }
+
+    let children = |parent: Option<usize>| -> Vec<usize> {
+        (0..entries.len())
+            .filter(|i| parents[*i] == parent)
+            .collect()
+    };
+    // All entries are open, so the count is the total number of descendants.
+    let mut counts = vec![0; entries.len()];
+    for i in (0..entries.len()).rev() {
+        if let Some(parent) = parents[i] {
+            counts[parent] += counts[i] + 1;
+        }
+    }
+
+    for (i, entry) in entries.iter().enumerate() {
+        let mut dict = Dictionary::new();
+        dict.set("Title", text_string(&entry.title));
+        dict.set(
+            "Parent",
+            Object::Reference(parents[i].map(|p| ids[p]).unwrap_or(outline_id)),
+        );
+        if let Some(dest) = dests.get(entry.page) {
+            dict.set("Dest", dest.to_object(entry.position));
+        }
+        let siblings = children(parents[i]);
+        let idx = siblings.iter().position(|j| *j == i).unwrap_or_default();
+        if idx > 0 {
+            dict.set("Prev", Object::Reference(ids[siblings[idx - 1]]));
+        }
+        if let Some(next) = siblings.get(idx + 1) {
+            dict.set("Next", Object::Reference(ids[*next]));
+        }
+        let own_children = children(Some(i));
+        if let (Some(first), Some(last)) = (own_children.first(), own_children.last()) {
+            dict.set("First", Object::Reference(ids[*first]));
+            dict.set("Last", Object::Reference(ids[*last]));
+            dict.set("Count", Object::Integer(counts[i] as i64));
+        }
+        doc.objects.insert(ids[i], Object::Dictionary(dict));
+    }
+
+    let mut outline = Dictionary::new();
+    outline.set("Type", Object::Name(b"Outlines".to_vec()));
+    let top_level = children(None);
+    if let (Some(first), Some(last)) = (top_level.first(), top_level.last()) {
+        outline.set("First", Object::Reference(ids[*first]));
+        outline.set("Last", Object::Reference(ids[*last]));
+    }
+    outline.set("Count", Object::Integer(entries.len() as i64));
+    doc.objects.insert(outline_id, Object::Dictionary(outline));
+
+    outline_id
+}
+
+/// Appends the given annotations to the annotations array of the given page.
+fn add_annotations(
+    doc: &mut lopdf::Document,
+    page_id: ObjectId,
+    mut annots: Vec<Object>,
+) -> Result<(), Error> {
+    let page = doc
+        .get_object_mut(page_id)
+        .and_then(Object::as_dict_mut)
+        .map_err(|err| Error::new("Could not find page dictionary", err))?;
+    if let Ok(Object::Array(existing)) = page.get_mut(b"Annots") {
+        existing.append(&mut annots);
+    } else {
+        page.set("Annots", Object::Array(annots));
+    }
+    Ok(())
+}
+
+/// Changes the order of the layers in the content stream of the given page.
+///
+/// `printpdf` writes the layers in the order of their creation, each enclosed in a marked-content
+/// sequence for its optional content group.  We reorder these sequences so that the layers are
+/// drawn in the given order.
+fn reorder_layers(
+    doc: &mut lopdf::Document,
+    page_id: ObjectId,
+    order: &[usize],
+) -> Result<(), Error> {
+    let content = doc
+        .get_and_decode_page_content(page_id)
+        .map_err(|err| Error::new("Could not decode the page content", err))?;
+    let mut layers: Vec<Vec<Operation>> = Vec::new();
+    let mut depth = 0usize;
+    for operation in content.operations {
+        match operation.operator.as_str() {
+            "BDC" | "BMC" => {
+                if depth == 0 {
+                    layers.push(Vec::new());
+                }
+                depth += 1;
+            }
+            "EMC" => depth = depth.saturating_sub(1),
+            _ => {}
+        }
+        layers
+            .last_mut()
+            .ok_or_else(|| {
+                Error::new("Unexpected content outside of a layer", ErrorKind::Internal)
+            })?
+            .push(operation);
+    }
+    if layers.len() != order.len() {
+        return Err(Error::new(
+            "The number of layers in the generated document does not match",
+            ErrorKind::Internal,
+        ));
+    }
+
+    let mut operations = Vec::new();
+    for idx in order {
+        operations.append(&mut layers[*idx]);
+    }
+    let content = Content { operations }
+        .encode()
+        .map_err(|err| Error::new("Could not encode the page content", err))?;
+    doc.change_page_content(page_id, content)
+        .map_err(|err| Error::new("Could not change the page content", err))
+}
+
+/// Sets the opacities of the graphics states of the given page.
+///
+/// `printpdf` names the graphics states of a page `GS0`, `GS1` etc. in the order of their
+/// creation.  (It also has an option to set the opacity, but it mixes up the keys for the fill and
+/// the stroke opacity, so we set them manually.)
+fn set_opacities(
+    doc: &mut lopdf::Document,
+    page_id: ObjectId,
+    opacities: &[Opacity],
+) -> Result<(), Error> {
+    let resources_id = doc
+        .get_object(page_id)
+        .and_then(Object::as_dict)
+        .and_then(|page| page.get(b"Resources"))
+        .and_then(Object::as_reference)
+        .map_err(|err| Error::new("Could not find the page resources", err))?;
+    let states = doc
+        .get_object_mut(resources_id)
+        .and_then(Object::as_dict_mut)
+        .and_then(|resources| resources.get_mut(b"ExtGState"))
+        .and_then(Object::as_dict_mut)
+        .map_err(|err| Error::new("Could not find the graphics states of the page", err))?;
+    for (idx, opacity) in opacities.iter().enumerate() {
+        let state = states
+            .get_mut(format!("GS{}", idx).as_bytes())
+            .and_then(Object::as_dict_mut)
+            .map_err(|err| Error::new("Could not find a graphics state", err))?;
+        state.set("ca", Object::Real(opacity.fill));
+        state.set("CA", Object::Real(opacity.stroke));
+    }
+    Ok(())
+}
+
+fn get_catalog(doc: &mut lopdf::Document) -> Result<&mut Dictionary, Error> {
+    let catalog_id = doc
+        .trailer
+        .get(b"Root")
+        .and_then(Object::as_reference)
+        .map_err(|err| Error::new("Could not find the document catalog", err))?;
+    doc.get_object_mut(catalog_id)
+        .and_then(Object::as_dict_mut)
+        .map_err(|err| Error::new("Could not find the document catalog", err))
+}
+
+/// Encodes the given string as a PDF text string.
+///
+/// ASCII strings are used as is, all other strings are encoded as UTF-16BE with a byte order mark.
+fn text_string(s: &str) -> Object {
+    if s.is_ascii() {
+        Object::String(s.as_bytes().to_vec(), StringFormat::Literal)
+    } else {
+        let mut bytes = vec![0xfe, 0xff];
+        for c in s.encode_utf16() {
+            bytes.extend_from_slice(&c.to_be_bytes());
+        }
+        Object::String(bytes, StringFormat::Hexadecimal)
+    }
+}
@@ -10,6 +10,9 @@
 //! A [`Style`][] is a combination of a [`FontFamily`][], a font size, a line spacing factor, a
 //! [`Color`][] and a combination of [`Effect`][]s (bold or italic).
 //!
+//! Styled strings can also have a [`Link`][] target that turns them into a clickable hyperlink,
+//! or a [`Footnote`][] that is printed at the bottom of the page.
+//!
 //! # Example
 //!
 //! ```
@@ -22,6 +25,8 @@
 //! [`Color`]: enum.Color.html
 //! [`Effect`]: enum.Effect.html
 //! [`FontFamily`]: ../fonts/struct.FontFamily.html
+//! [`Footnote`]: struct.Footnote.html
+//! [`Link`]: enum.Link.html
 //! [`Style`]: struct.Style.html
 //! [`StyledCow`]: struct.StyledCow.html
 //! [`StyledStr`]: struct.StyledStr.html
@@ -80,13 +85,94 @@ impl From<Color> for printpdf::Color {
     }
 }
 
-/// A text effect (bold or italic).
+/// A text effect (bold, italic, a decoration line or a baseline shift).
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub enum Effect {
     /// Bold text.
     Bold,
     /// Italic text.
     Italic,
+    /// Underlined text.
+    Underline,
+    /// Struck-through text.
+    Strikethrough,
+    /// Overlined text.
+    Overline,
+    /// Superscript text that is smaller and raised above the baseline.
+    Superscript,
+    /// Subscript text that is smaller and lowered below the baseline.
+    Subscript,
+}
+
+/// A blend mode that determines how colors are combined with the colors that have already been
+/// drawn beneath them.
+///
+/// See section 11.3.5 of the [PDF specification][] for the definition of the blend modes.
+///
+/// [PDF specification]: https://www.adobe.com/content/dam/acom/en/devnet/pdf/pdfs/PDF32000_2008.pdf
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BlendMode {
+    /// The new color replaces the old color.
+    Normal,
+    /// The colors are multiplied, so the result is always at least as dark as the old color.
+    Multiply,
+    /// The complements of the colors are multiplied, so the result is always at least as light as
+    /// the old color.
+    Screen,
+    /// The colors are multiplied or screened, depending on the old color.
+    Overlay,
+    /// The darker color is selected.
+    Darken,
+    /// The lighter color is selected.
+    Lighten,
+    /// The old color is brightened to reflect the new color.
+    ColorDodge,
+    /// The old color is darkened to reflect the new color.
+    ColorBurn,
+    /// The colors are multiplied or screened, depending on the new color.
+    HardLight,
+    /// The colors are darkened or lightened, depending on the new color.
+    SoftLight,
+    /// The darker color is subtracted from the lighter color.
+    Difference,
+    /// Like `Difference`, but with a lower contrast.
+    Exclusion,
+}
+
+// #[default] on enum variants requires Rust 1.62, so we can't derive Default.
+#[allow(clippy::derivable_impls)]
+impl Default for BlendMode {
+    fn default() -> BlendMode {
+        BlendMode::Normal
+    }
+}
+
+impl From<BlendMode> for printpdf::BlendMode {
+    fn from(blend_mode: BlendMode) -> printpdf::BlendMode {
+        use printpdf::SeperableBlendMode as Mode;
+
+        printpdf::BlendMode::Seperable(match blend_mode {
+            BlendMode::Normal => Mode::Normal,
+            BlendMode::Multiply => Mode::Multiply,
+            BlendMode::Screen => Mode::Screen,
+            BlendMode::Overlay => Mode::Overlay,
+            BlendMode::Darken => Mode::Darken,
+            BlendMode::Lighten => Mode::Lighten,
+            BlendMode::ColorDodge => Mode::ColorDodge,
+            BlendMode::ColorBurn => Mode::ColorBurn,
+            BlendMode::HardLight => Mode::HardLight,
+            BlendMode::SoftLight => Mode::SoftLight,
+            BlendMode::Difference => Mode::Difference,
+            BlendMode::Exclusion => Mode::Exclusion,
+        })
+    }
+}
+
+/// Limits the given opacity to the range from 0 to 1.
+// f64::clamp requires Rust 1.50.
+#[allow(clippy::manual_clamp)]
+fn limit_opacity(opacity: f64) -> f64 {
+    opacity.max(0.0).min(1.0)
 }
 
 /// A style annotation for a string.
@@ -97,14 +183,40 @@ pub enum Effect {
 /// - a line spacing factor, with 1 meaning single line spacing (defaults to 1)
 /// - an outline color, see [`Color`][] (defaults to black)
 /// - a combination of text effects, see [`Effect`][] (defaults to none)
+/// - a fill and a stroke opacity between 0 (transparent) and 1 (opaque) (defaults to 1)
+/// - a blend mode, see [`BlendMode`][] (defaults to [`BlendMode::Normal`][])
 ///
 /// All properties are optional.  If they are not set, they can be inferred from parent styles or
 /// from the defaults.
 ///
+/// The decoration lines (underline, strikethrough and overline) are drawn in the text color using
+/// the metrics provided by the font, see [`Font::underline_metrics`][].  Superscript and subscript
+/// text uses a smaller font size and a shifted baseline, see [`Font::superscript_metrics`][].  The
+/// superscript and the subscript effect are mutually exclusive; setting one of them removes the
+/// other one.
+///
+/// Text is drawn with the fill opacity.  The opacities and the blend mode are also used for the
+/// lines and shapes drawn by elements with this style, unless the elements set their own values.
+///
+/// # Example
+///
+/// ```
+/// use genpdf::style;
+/// let watermark = style::Style::new()
+///     .with_font_size(72)
+///     .with_color(style::Color::Rgb(255, 0, 0))
+///     .with_opacity(0.3)
+///     .with_blend_mode(style::BlendMode::Multiply);
+/// ```
+///
+/// [`BlendMode`]: enum.BlendMode.html
+/// [`BlendMode::Normal`]: enum.BlendMode.html#variant.Normal
 /// [`Color`]: enum.Color.html
 /// [`Effect`]: enum.Effect.html
 /// [`FontFamily`]: ../fonts/struct.FontFamily.html
 /// [`FontCache`]: ../fonts/struct.FontCache.html
+/// [`Font::underline_metrics`]: ../fonts/struct.Font.html#method.underline_metrics
+/// [`Font::superscript_metrics`]: ../fonts/struct.Font.html#method.superscript_metrics
 #[derive(Clone, Copy, Debug, Default, PartialEq)]
 pub struct Style {
     font_family: Option<fonts::FontFamily<fonts::Font>>,
@@ -113,6 +225,14 @@ pub struct Style {
     color: Option<Color>,
     is_bold: bool,
     is_italic: bool,
+    is_underline: bool,
+    is_strikethrough: bool,
+    is_overline: bool,
+    is_superscript: bool,
+    is_subscript: bool,
+    fill_opacity: Option<f64>,
+    stroke_opacity: Option<f64>,
+    blend_mode: Option<BlendMode>,
 }
 
 impl Style {
@@ -139,6 +259,30 @@ impl Style {
         if style.is_italic {
             self.is_italic = true;
         }
+        if style.is_underline {
+            self.is_underline = true;
+        }
+        if style.is_strikethrough {
+            self.is_strikethrough = true;
+        }
+        if style.is_overline {
+            self.is_overline = true;
+        }
+        if style.is_superscript {
+            self.set_superscript();
+        }
+        if style.is_subscript {
+            self.set_subscript();
+        }
+        if let Some(fill_opacity) = style.fill_opacity {
+            self.fill_opacity = Some(fill_opacity);
+        }
+        if let Some(stroke_opacity) = style.stroke_opacity {
+            self.stroke_opacity = Some(stroke_opacity);
+        }
+        if let Some(blend_mode) = style.blend_mode {
+            self.blend_mode = Some(blend_mode);
+        }
     }
 
     /// Combines this style and the given style and returns the result.
@@ -167,7 +311,37 @@ impl Style {
         self.is_italic
     }
 
+    /// Returns whether the underline text effect is set.
+    pub fn is_underline(&self) -> bool {
+        self.is_underline
+    }
+
+    /// Returns whether the strikethrough text effect is set.
+    pub fn is_strikethrough(&self) -> bool {
+        self.is_strikethrough
+    }
+
+    /// Returns whether the overline text effect is set.
+    pub fn is_overline(&self) -> bool {
+        self.is_overline
+    }
+
+    /// Returns whether the superscript text effect is set.
+    pub fn is_superscript(&self) -> bool {
+        self.is_superscript
+    }
+
+    /// Returns whether the subscript text effect is set.
+    pub fn is_subscript(&self) -> bool {
+        self.is_subscript
+    }
+
     /// Returns the font size for this style in points, or 12 if no font size is set.
+    ///
+    /// For superscript and subscript text, this is the font size of the surrounding text.  The
+    /// font size used for rendering is returned by [`script_metrics`][].
+    ///
+    /// [`script_metrics`]: #method.script_metrics
     pub fn font_size(&self) -> u8 {
         self.font_size.unwrap_or(12)
     }
@@ -177,6 +351,23 @@ impl Style {
         self.line_spacing.unwrap_or(1.0)
     }
 
+    /// Returns the fill opacity for this style, or 1 if no fill opacity is set.
+    pub fn fill_opacity(&self) -> f64 {
+        self.fill_opacity.unwrap_or(1.0)
+    }
+
+    /// Returns the stroke opacity for this style, or 1 if no stroke opacity is set.
+    pub fn stroke_opacity(&self) -> f64 {
+        self.stroke_opacity.unwrap_or(1.0)
+    }
+
+    /// Returns the blend mode for this style, or [`BlendMode::Normal`][] if no blend mode is set.
+    ///
+    /// [`BlendMode::Normal`]: enum.BlendMode.html#variant.Normal
+    pub fn blend_mode(&self) -> BlendMode {
+        self.blend_mode.unwrap_or_default()
+    }
+
     /// Sets the bold effect for this style.
     pub fn set_bold(&mut self) {
         self.is_bold = true;
@@ -199,6 +390,65 @@ impl Style {
         self
     }
 
+    /// Sets the underline effect for this style.
+    pub fn set_underline(&mut self) {
+        self.is_underline = true;
+    }
+
+    /// Sets the underline effect for this style and returns it.
+    pub fn underline(mut self) -> Style {
+        self.set_underline();
+        self
+    }
+
+    /// Sets the strikethrough effect for this style.
+    pub fn set_strikethrough(&mut self) {
+        self.is_strikethrough = true;
+    }
+
+    /// Sets the strikethrough effect for this style and returns it.
+    pub fn strikethrough(mut self) -> Style {
+        self.set_strikethrough();
+        self
+    }
+
+    /// Sets the overline effect for this style.
+    pub fn set_overline(&mut self) {
+        self.is_overline = true;
+    }
+
+    /// Sets the overline effect for this style and returns it.
+    pub fn overline(mut self) -> Style {
+        self.set_overline();
+        self
+    }
+
+    /// Sets the superscript effect for this style and removes the subscript effect.
+    pub fn set_superscript(&mut self) {
+        self.is_superscript = true;
+        self.is_subscript = false;
+    }
+
+    /// Sets the superscript effect for this style, removes the subscript effect and returns the
+    /// style.
+    pub fn superscript(mut self) -> Style {
+        self.set_superscript();
+        self
+    }
+
+    /// Sets the subscript effect for this style and removes the superscript effect.
+    pub fn set_subscript(&mut self) {
+        self.is_subscript = true;
+        self.is_superscript = false;
+    }
+
+    /// Sets the subscript effect for this style, removes the superscript effect and returns the
+    /// style.
+    pub fn subscript(mut self) -> Style {
+        self.set_subscript();
+        self
+    }
+
     /// Sets the font family for this style.
     pub fn set_font_family(&mut self, font_family: fonts::FontFamily<fonts::Font>) {
         self.font_family = Some(font_family);
@@ -243,6 +493,57 @@ impl Style {
         self
     }
 
+    /// Sets the fill and the stroke opacity for this style.
+    ///
+    /// The opacity is limited to the range from 0 (transparent) to 1 (opaque).
+    pub fn set_opacity(&mut self, opacity: f64) {
+        self.set_fill_opacity(opacity);
+        self.set_stroke_opacity(opacity);
+    }
+
+    /// Sets the fill and the stroke opacity for this style and returns it.
+    pub fn with_opacity(mut self, opacity: f64) -> Style {
+        self.set_opacity(opacity);
+        self
+    }
+
+    /// Sets the fill opacity for this style.
+    ///
+    /// The opacity is limited to the range from 0 (transparent) to 1 (opaque).
+    pub fn set_fill_opacity(&mut self, opacity: f64) {
+        self.fill_opacity = Some(limit_opacity(opacity));
+    }
+
+    /// Sets the fill opacity for this style and returns it.
+    pub fn with_fill_opacity(mut self, opacity: f64) -> Style {
+        self.set_fill_opacity(opacity);
+        self
+    }
+
+    /// Sets the stroke opacity for this style.
+    ///
+    /// The opacity is limited to the range from 0 (transparent) to 1 (opaque).
+    pub fn set_stroke_opacity(&mut self, opacity: f64) {
+        self.stroke_opacity = Some(limit_opacity(opacity));
+    }
+
+    /// Sets the stroke opacity for this style and returns it.
+    pub fn with_stroke_opacity(mut self, opacity: f64) -> Style {
+        self.set_stroke_opacity(opacity);
+        self
+    }
+
+    /// Sets the blend mode for this style.
+    pub fn set_blend_mode(&mut self, blend_mode: BlendMode) {
+        self.blend_mode = Some(blend_mode);
+    }
+
+    /// Sets the blend mode for this style and returns it.
+    pub fn with_blend_mode(mut self, blend_mode: BlendMode) -> Style {
+        self.set_blend_mode(blend_mode);
+        self
+    }
+
     /// Calculates the width of the given character with this style using the data in the given
     /// font cache.
     ///
@@ -251,7 +552,7 @@ impl Style {
     /// [`FontCache`]: ../fonts/struct.FontCache.html
     pub fn char_width(&self, font_cache: &fonts::FontCache, c: char) -> Mm {
         self.font(font_cache)
-            .char_width(font_cache, c, self.font_size())
+            .char_width(font_cache, c, self.rendered_font_size(font_cache))
     }
 
     /// Calculates the width of the given string with this style using the data in the given font
@@ -262,8 +563,9 @@ impl Style {
     /// [`FontCache`]: ../fonts/struct.FontCache.html
     pub fn str_width(&self, font_cache: &fonts::FontCache, s: &str) -> Mm {
         let font = self.font(font_cache);
+        let font_size = self.rendered_font_size(font_cache);
         s.chars()
-            .map(|c| font.char_width(font_cache, c, self.font_size()))
+            .map(|c| font.char_width(font_cache, c, font_size))
             .sum()
     }
 
@@ -293,8 +595,44 @@ impl Style {
     /// If the font family is set, it must have been created by the given [`FontCache`][].
     ///
     /// [`FontCache`]: ../fonts/struct.FontCache.html
+    ///
+    /// For superscript and subscript text, the line height is increased if the shifted text
+    /// exceeds the ascent or the descent of the surrounding text.
     pub fn line_height(&self, font_cache: &fonts::FontCache) -> Mm {
-        self.font(font_cache).get_line_height(self.font_size()) * self.line_spacing()
+        let font = self.font(font_cache);
+        let font_size = self.font_size();
+        let mut line_height = font.get_line_height(font_size);
+        if let Some(metrics) = self.script_metrics(font_cache) {
+            let top = metrics.offset + font.ascent(metrics.font_size) - font.ascent(font_size);
+            let bottom = font.descent(metrics.font_size) - metrics.offset - font.descent(font_size);
+            line_height += top.max(Mm(0.0)) + bottom.max(Mm(0.0));
+        }
+        line_height * self.line_spacing()
+    }
+
+    /// Returns the font size and the baseline offset for superscript or subscript text with this
+    /// style using the data in the given font cache, or `None` if neither the superscript nor the
+    /// subscript effect is set.
+    ///
+    /// If the font family is set, it must have been created by the given [`FontCache`][].
+    ///
+    /// [`FontCache`]: ../fonts/struct.FontCache.html
+    pub fn script_metrics(&self, font_cache: &fonts::FontCache) -> Option<fonts::ScriptMetrics> {
+        if self.is_superscript {
+            Some(self.font(font_cache).superscript_metrics(self.font_size()))
+        } else if self.is_subscript {
+            Some(self.font(font_cache).subscript_metrics(self.font_size()))
+        } else {
+            None
+        }
+    }
+
+    /// Returns the font size that is used to render text with this style, taking into account the
+    /// superscript and subscript effects.
+    fn rendered_font_size(&self, font_cache: &fonts::FontCache) -> u8 {
+        self.script_metrics(font_cache)
+            .map(|metrics| metrics.font_size)
+            .unwrap_or_else(|| self.font_size())
     }
 }
 
@@ -310,6 +648,11 @@ impl From<Effect> for Style {
         match effect {
             Effect::Bold => style.bold(),
             Effect::Italic => style.italic(),
+            Effect::Underline => style.underline(),
+            Effect::Strikethrough => style.strikethrough(),
+            Effect::Overline => style.overline(),
+            Effect::Superscript => style.superscript(),
+            Effect::Subscript => style.subscript(),
         }
     }
 }
@@ -336,6 +679,701 @@ impl<T: Into<Style>> iter::FromIterator<T> for Style {
     }
 }
 
+/// The style of a stroked line:  its thickness, color, opacity, blend mode, dash pattern, cap and
+/// join style.
+///
+/// Line styles are used by [`Area::draw_line`][], by the outline of shapes (see
+/// [`ShapeStyle`][]) and by the frames drawn by [`FramedElement`][] and
+/// [`FrameCellDecorator`][].  By default, lines are solid, opaque, 1 pt (about 0.35 mm) thick and
+/// have butt caps and miter joins.  If no color is set, lines are drawn in black, or in the color
+/// of the current style if they are drawn by an element.  Similarly, elements use the stroke
+/// opacity and the blend mode of the current style if the line style is opaque or uses the normal
+/// blend mode.
+///
+/// # Example
+///
+/// ```
+/// use genpdf::style;
+/// let dashed = style::LineStyle::new()
+///     .with_thickness(0.5)
+///     .with_color(style::Color::Rgb(128, 128, 128))
+///     .with_dash_pattern(vec![genpdf::Mm::from(2), genpdf::Mm::from(1)])
+///     .with_cap(style::LineCap::Round);
+/// ```
+///
+/// [`Area::draw_line`]: ../render/struct.Area.html#method.draw_line
+/// [`ShapeStyle`]: struct.ShapeStyle.html
+/// [`FramedElement`]: ../elements/struct.FramedElement.html
+/// [`FrameCellDecorator`]: ../elements/struct.FrameCellDecorator.html
+#[derive(Clone, Debug, PartialEq)]
+pub struct LineStyle {
+    thickness: Mm,
+    color: Option<Color>,
+    opacity: f64,
+    blend_mode: BlendMode,
+    dash_pattern: Vec<Mm>,
+    dash_offset: Mm,
+    cap: LineCap,
+    join: LineJoin,
+}
+
+impl LineStyle {
+    /// The default line thickness of 1 pt.
+    pub const DEFAULT_THICKNESS: Mm = Mm(0.352_777_8);
+
+    /// Creates a new solid line style with the default thickness and without a color.
+    pub fn new() -> LineStyle {
+        LineStyle {
+            thickness: LineStyle::DEFAULT_THICKNESS,
+            color: None,
+            opacity: 1.0,
+            blend_mode: BlendMode::default(),
+            dash_pattern: Vec::new(),
+            dash_offset: Mm::default(),
+            cap: LineCap::default(),
+            join: LineJoin::default(),
+        }
+    }
+
+    /// Returns the thickness of this line style.
+    pub fn thickness(&self) -> Mm {
+        self.thickness
+    }
+
+    /// Sets the thickness of this line style.
+    pub fn set_thickness(&mut self, thickness: impl Into<Mm>) {
+        self.thickness = thickness.into();
+    }
+
+    /// Sets the thickness of this line style and returns the line style.
+    pub fn with_thickness(mut self, thickness: impl Into<Mm>) -> LineStyle {
+        self.set_thickness(thickness);
+        self
+    }
+
+    /// Returns the color of this line style, if set.
+    pub fn color(&self) -> Option<Color> {
+        self.color
+    }
+
+    /// Sets the color of this line style.
+    pub fn set_color(&mut self, color: impl Into<Option<Color>>) {
+        self.color = color.into();
+    }
+
+    /// Sets the color of this line style and returns the line style.
+    pub fn with_color(mut self, color: impl Into<Option<Color>>) -> LineStyle {
+        self.set_color(color);
+        self
+    }
+
+    /// Returns the opacity of this line style.
+    pub fn opacity(&self) -> f64 {
+        self.opacity
+    }
+
+    /// Sets the opacity of this line style.
+    ///
+    /// The opacity is limited to the range from 0 (transparent) to 1 (opaque).
+    pub fn set_opacity(&mut self, opacity: f64) {
+        self.opacity = limit_opacity(opacity);
+    }
+
+    /// Sets the opacity of this line style and returns the line style.
+    pub fn with_opacity(mut self, opacity: f64) -> LineStyle {
+        self.set_opacity(opacity);
+        self
+    }
+
+    /// Returns the blend mode of this line style.
+    pub fn blend_mode(&self) -> BlendMode {
+        self.blend_mode
+    }
+
+    /// Sets the blend mode of this line style.
+    pub fn set_blend_mode(&mut self, blend_mode: BlendMode) {
+        self.blend_mode = blend_mode;
+    }
+
+    /// Sets the blend mode of this line style and returns the line style.
+    pub fn with_blend_mode(mut self, blend_mode: BlendMode) -> LineStyle {
+        self.set_blend_mode(blend_mode);
+        self
+    }
+
+    /// Returns the dash pattern of this line style, or an empty slice for solid lines.
+    pub fn dash_pattern(&self) -> &[Mm] {
+        &self.dash_pattern
+    }
+
+    /// Returns the offset of the dash pattern of this line style.
+    pub fn dash_offset(&self) -> Mm {
+        self.dash_offset
+    }
+
+    /// Sets the dash pattern of this line style.
+    ///
+    /// The dash pattern alternates between the lengths of the dashes and the gaps, starting with
+    /// a dash.  If the pattern is empty, the line is solid.  Only the first six lengths are used
+    /// and all lengths are rounded to full points (1 pt is about 0.35 mm), as these are the limits
+    /// of the PDF backend.  Lengths below one point are increased to one point, so the finest
+    /// possible pattern is a dotted line with dashes and gaps of one point.
+    pub fn set_dash_pattern(&mut self, dash_pattern: Vec<Mm>) {
+        self.dash_pattern = dash_pattern;
+    }
+
+    /// Sets the dash pattern of this line style and returns the line style.
+    ///
+    /// See [`set_dash_pattern`][] for more information.
+    ///
+    /// [`set_dash_pattern`]: #method.set_dash_pattern
+    pub fn with_dash_pattern(mut self, dash_pattern: Vec<Mm>) -> LineStyle {
+        self.set_dash_pattern(dash_pattern);
+        self
+    }
+
+    /// Sets the distance into the dash pattern at which the line starts.
+    pub fn set_dash_offset(&mut self, offset: impl Into<Mm>) {
+        self.dash_offset = offset.into();
+    }
+
+    /// Sets the distance into the dash pattern at which the line starts and returns the line
+    /// style.
+    pub fn with_dash_offset(mut self, offset: impl Into<Mm>) -> LineStyle {
+        self.set_dash_offset(offset);
+        self
+    }
+
+    /// Returns the cap style of this line style.
+    pub fn cap(&self) -> LineCap {
+        self.cap
+    }
+
+    /// Sets the cap style of this line style.
+    pub fn set_cap(&mut self, cap: LineCap) {
+        self.cap = cap;
+    }
+
+    /// Sets the cap style of this line style and returns the line style.
+    pub fn with_cap(mut self, cap: LineCap) -> LineStyle {
+        self.set_cap(cap);
+        self
+    }
+
+    /// Returns the join style of this line style.
+    pub fn join(&self) -> LineJoin {
+        self.join
+    }
+
+    /// Sets the join style of this line style.
+    pub fn set_join(&mut self, join: LineJoin) {
+        self.join = join;
+    }
+
+    /// Sets the join style of this line style and returns the line style.
+    pub fn with_join(mut self, join: LineJoin) -> LineStyle {
+        self.set_join(join);
+        self
+    }
+}
+
+impl Default for LineStyle {
+    fn default() -> LineStyle {
+        LineStyle::new()
+    }
+}
+
+impl From<Color> for LineStyle {
+    fn from(color: Color) -> LineStyle {
+        LineStyle::new().with_color(color)
+    }
+}
+
+impl From<Style> for LineStyle {
+    fn from(style: Style) -> LineStyle {
+        LineStyle::new()
+            .with_color(style.color())
+            .with_opacity(style.stroke_opacity())
+            .with_blend_mode(style.blend_mode())
+    }
+}
+
+/// The shape at the ends of a stroked line.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LineCap {
+    /// The line is squared off at its end point.
+    Butt,
+    /// The line ends with a semicircle around its end point.
+    Round,
+    /// The line is extended beyond its end point by half of its thickness and squared off.
+    Square,
+}
+
+// #[default] on enum variants requires Rust 1.62, so we can't derive Default.
+#[allow(clippy::derivable_impls)]
+impl Default for LineCap {
+    fn default() -> LineCap {
+        LineCap::Butt
+    }
+}
+
+/// The shape at the corners of a stroked line.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LineJoin {
+    /// The outer edges of the line are extended until they meet.
+    Miter,
+    /// The corner is rounded.
+    Round,
+    /// The corner is cut off.
+    Bevel,
+}
+
+// #[default] on enum variants requires Rust 1.62, so we can't derive Default.
+#[allow(clippy::derivable_impls)]
+impl Default for LineJoin {
+    fn default() -> LineJoin {
+        LineJoin::Miter
+    }
+}
+
+/// The fill color and the outline of a shape.
+///
+/// Shapes can be drawn with the methods of [`Area`][], for example [`Area::draw_rectangle`][], or
+/// with the [`Shape`][] element.  If the fill color is set, the shape is filled with this color.
+/// If the line style is set, the outline of the shape is drawn with this [`LineStyle`][].  By
+/// default, shapes are not filled and have a solid black outline.
+///
+/// The fill is drawn with the fill opacity of the shape style and the outline with the opacity of
+/// the line style.  The blend mode of the shape style is used for both the fill and the outline;
+/// the blend mode of the line style is ignored.
+///
+/// # Examples
+///
+/// ```
+/// use genpdf::style;
+/// let badge = style::ShapeStyle::new()
+///     .with_fill_color(style::Color::Rgb(0, 128, 0))
+///     .with_line_style(style::LineStyle::new().with_thickness(1));
+/// ```
+///
+/// A lightly tinted highlight box:
+///
+/// ```
+/// use genpdf::style;
+/// let highlight = style::ShapeStyle::new()
+///     .with_fill_color(style::Color::Rgb(255, 255, 0))
+///     .with_fill_opacity(0.25)
+///     .with_blend_mode(style::BlendMode::Multiply)
+///     .with_stroke_color(None);
+/// ```
+///
+/// [`Area`]: ../render/struct.Area.html
+/// [`Area::draw_rectangle`]: ../render/struct.Area.html#method.draw_rectangle
+/// [`Shape`]: ../elements/struct.Shape.html
+/// [`LineStyle`]: struct.LineStyle.html
+#[derive(Clone, Debug, PartialEq)]
+pub struct ShapeStyle {
+    fill_color: Option<Color>,
+    fill_opacity: f64,
+    blend_mode: BlendMode,
+    line_style: Option<LineStyle>,
+}
+
+impl ShapeStyle {
+    /// Creates a new shape style without a fill color and with a solid black outline.
+    pub fn new() -> ShapeStyle {
+        ShapeStyle {
+            fill_color: None,
+            fill_opacity: 1.0,
+            blend_mode: BlendMode::default(),
+            line_style: Some(LineStyle::new()),
+        }
+    }
+
+    /// Returns the fill color of this style, or `None` if shapes are not filled.
+    pub fn fill_color(&self) -> Option<Color> {
+        self.fill_color
+    }
+
+    /// Returns the stroke color of this style, or `None` if no outline is drawn.
+    pub fn stroke_color(&self) -> Option<Color> {
+        self.line_style
+            .as_ref()
+            .map(|line_style| line_style.color().unwrap_or(Color::Rgb(0, 0, 0)))
+    }
+
+    /// Returns the fill opacity of this style.
+    pub fn fill_opacity(&self) -> f64 {
+        self.fill_opacity
+    }
+
+    /// Returns the blend mode of this style.
+    pub fn blend_mode(&self) -> BlendMode {
+        self.blend_mode
+    }
+
+    /// Returns the line style of the outline, or `None` if no outline is drawn.
+    pub fn line_style(&self) -> Option<&LineStyle> {
+        self.line_style.as_ref()
+    }
+
+    /// Sets the fill color of this style.
+    pub fn set_fill_color(&mut self, color: impl Into<Option<Color>>) {
+        self.fill_color = color.into();
+    }
+
+    /// Sets the fill color of this style and returns the style.
+    pub fn with_fill_color(mut self, color: impl Into<Option<Color>>) -> ShapeStyle {
+        self.set_fill_color(color);
+        self
+    }
+
+    /// Sets the fill opacity of this style and the opacity of its line style, if set.
+    ///
+    /// The opacity is limited to the range from 0 (transparent) to 1 (opaque).
+    pub fn set_opacity(&mut self, opacity: f64) {
+        self.set_fill_opacity(opacity);
+        if let Some(line_style) = &mut self.line_style {
+            line_style.set_opacity(opacity);
+        }
+    }
+
+    /// Sets the fill opacity of this style and the opacity of its line style, if set, and returns
+    /// the style.
+    pub fn with_opacity(mut self, opacity: f64) -> ShapeStyle {
+        self.set_opacity(opacity);
+        self
+    }
+
+    /// Sets the fill opacity of this style.
+    ///
+    /// The opacity is limited to the range from 0 (transparent) to 1 (opaque).
+    pub fn set_fill_opacity(&mut self, opacity: f64) {
+        self.fill_opacity = limit_opacity(opacity);
+    }
+
+    /// Sets the fill opacity of this style and returns the style.
+    pub fn with_fill_opacity(mut self, opacity: f64) -> ShapeStyle {
+        self.set_fill_opacity(opacity);
+        self
+    }
+
+    /// Sets the blend mode of this style.
+    pub fn set_blend_mode(&mut self, blend_mode: BlendMode) {
+        self.blend_mode = blend_mode;
+    }
+
+    /// Sets the blend mode of this style and returns the style.
+    pub fn with_blend_mode(mut self, blend_mode: BlendMode) -> ShapeStyle {
+        self.set_blend_mode(blend_mode);
+        self
+    }
+
+    /// Sets the stroke color of this style.
+    ///
+    /// If the color is `None`, no outline is drawn.  Otherwise, the color of the line style is
+    /// set to the given color.
+    pub fn set_stroke_color(&mut self, color: impl Into<Option<Color>>) {
+        self.line_style = color
+            .into()
+            .map(|color| self.line_style.take().unwrap_or_default().with_color(color));
+    }
+
+    /// Sets the stroke color of this style and returns the style.
+    pub fn with_stroke_color(mut self, color: impl Into<Option<Color>>) -> ShapeStyle {
+        self.set_stroke_color(color);
+        self
+    }
+
+    /// Sets the line style of the outline.  If the line style is `None`, no outline is drawn.
+    pub fn set_line_style(&mut self, line_style: impl Into<Option<LineStyle>>) {
+        self.line_style = line_style.into();
+    }
+
+    /// Sets the line style of the outline and returns the style.
+    pub fn with_line_style(mut self, line_style: impl Into<Option<LineStyle>>) -> ShapeStyle {
+        self.set_line_style(line_style);
+        self
+    }
+}
+
+impl Default for ShapeStyle {
+    fn default() -> ShapeStyle {
+        ShapeStyle::new()
+    }
+}
+
+impl From<Color> for ShapeStyle {
+    fn from(color: Color) -> ShapeStyle {
+        ShapeStyle::new().with_stroke_color(color)
+    }
+}
+
+impl From<LineStyle> for ShapeStyle {
+    fn from(line_style: LineStyle) -> ShapeStyle {
+        ShapeStyle::new().with_line_style(line_style)
+    }
+}
+
+/// The decoration of a box around an element:  its background color, borders and corner radius.
+///
+/// Box decorations are drawn by the [`DecoratedElement`][] wrapper, see
+/// [`Element::decorated`][].  The background is drawn beneath the content of the element.  Each
+/// side of the box can have a border with its own [`LineStyle`][].  If the corner radius is set,
+/// the corners of the background and the borders are rounded.
+///
+/// # Example
+///
+/// ```
+/// use genpdf::style;
+/// let note = style::BoxDecoration::new()
+///     .with_background_color(style::Color::Rgb(255, 250, 220))
+///     .with_left_border(style::LineStyle::new().with_thickness(1))
+///     .with_corner_radius(2);
+/// ```
+///
+/// [`DecoratedElement`]: ../elements/struct.DecoratedElement.html
+/// [`Element::decorated`]: ../trait.Element.html#method.decorated
+/// [`LineStyle`]: struct.LineStyle.html
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct BoxDecoration {
+    background_color: Option<Color>,
+    // top, right, bottom, left
+    borders: [Option<LineStyle>; 4],
+    corner_radius: Mm,
+}
+
+impl BoxDecoration {
+    /// Creates a new box decoration without a background and without borders.
+    pub fn new() -> BoxDecoration {
+        BoxDecoration::default()
+    }
+
+    /// Returns the background color of this decoration, if set.
+    pub fn background_color(&self) -> Option<Color> {
+        self.background_color
+    }
+
+    /// Sets the background color of this decoration.
+    pub fn set_background_color(&mut self, color: impl Into<Option<Color>>) {
+        self.background_color = color.into();
+    }
+
+    /// Sets the background color of this decoration and returns the decoration.
+    pub fn with_background_color(mut self, color: impl Into<Option<Color>>) -> BoxDecoration {
+        self.set_background_color(color);
+        self
+    }
+
+    /// Returns the line style of the top border, if set.
+    pub fn top_border(&self) -> Option<&LineStyle> {
+        self.borders[0].as_ref()
+    }
+
+    /// Returns the line style of the right border, if set.
+    pub fn right_border(&self) -> Option<&LineStyle> {
+        self.borders[1].as_ref()
+    }
+
+    /// Returns the line style of the bottom border, if set.
+    pub fn bottom_border(&self) -> Option<&LineStyle> {
+        self.borders[2].as_ref()
+    }
+
+    /// Returns the line style of the left border, if set.
+    pub fn left_border(&self) -> Option<&LineStyle> {
+        self.borders[3].as_ref()
+    }
+
+    /// Sets the line style of all four borders of this decoration.
+    pub fn set_border(&mut self, line_style: impl Into<Option<LineStyle>>) {
+        let line_style = line_style.into();
+        self.borders = [
+            line_style.clone(),
+            line_style.clone(),
+            line_style.clone(),
+            line_style,
+        ];
+    }
+
+    /// Sets the line style of all four borders of this decoration and returns the decoration.
+    pub fn with_border(mut self, line_style: impl Into<Option<LineStyle>>) -> BoxDecoration {
+        self.set_border(line_style);
+        self
+    }
+
+    /// Sets the line style of the top border of this decoration.
+    pub fn set_top_border(&mut self, line_style: impl Into<Option<LineStyle>>) {
+        self.borders[0] = line_style.into();
+    }
+
+    /// Sets the line style of the top border of this decoration and returns the decoration.
+    pub fn with_top_border(mut self, line_style: impl Into<Option<LineStyle>>) -> BoxDecoration {
+        self.set_top_border(line_style);
+        self
+    }
+
+    /// Sets the line style of the right border of this decoration.
+    pub fn set_right_border(&mut self, line_style: impl Into<Option<LineStyle>>) {
+        self.borders[1] = line_style.into();
+    }
+
+    /// Sets the line style of the right border of this decoration and returns the decoration.
+    pub fn with_right_border(mut self, line_style: impl Into<Option<LineStyle>>) -> BoxDecoration {
+        self.set_right_border(line_style);
+        self
+    }
+
+    /// Sets the line style of the bottom border of this decoration.
+    pub fn set_bottom_border(&mut self, line_style: impl Into<Option<LineStyle>>) {
+        self.borders[2] = line_style.into();
+    }
+
+    /// Sets the line style of the bottom border of this decoration and returns the decoration.
+    pub fn with_bottom_border(mut self, line_style: impl Into<Option<LineStyle>>) -> BoxDecoration {
+        self.set_bottom_border(line_style);
+        self
+    }
+
+    /// Sets the line style of the left border of this decoration.
+    pub fn set_left_border(&mut self, line_style: impl Into<Option<LineStyle>>) {
+        self.borders[3] = line_style.into();
+    }
+
+    /// Sets the line style of the left border of this decoration and returns the decoration.
+    pub fn with_left_border(mut self, line_style: impl Into<Option<LineStyle>>) -> BoxDecoration {
+        self.set_left_border(line_style);
+        self
+    }
+
+    /// Returns the corner radius of this decoration.
+    pub fn corner_radius(&self) -> Mm {
+        self.corner_radius
+    }
+
+    /// Sets the corner radius of this decoration.
+    pub fn set_corner_radius(&mut self, radius: impl Into<Mm>) {
+        self.corner_radius = radius.into();
+    }
+
+    /// Sets the corner radius of this decoration and returns the decoration.
+    pub fn with_corner_radius(mut self, radius: impl Into<Mm>) -> BoxDecoration {
+        self.set_corner_radius(radius);
+        self
+    }
+}
+
+impl From<Color> for BoxDecoration {
+    fn from(color: Color) -> BoxDecoration {
+        BoxDecoration::new().with_background_color(color)
+    }
+}
+
+/// The target of a hyperlink.
+///
+/// Links can be attached to styled strings, see for example [`StyledString::with_link`][].  If a
+/// styled string with a link is printed, a link annotation is added to the document that covers
+/// the printed text.
+///
+/// # Examples
+///
+/// ```
+/// use genpdf::style;
+/// let docs = style::StyledString::new("documentation", style::Effect::Italic)
+///     .with_link(style::Link::Uri("https://docs.rs/genpdf".to_owned()));
+/// let intro = style::StyledString::from("introduction")
+///     .with_link(style::Link::Destination("intro".to_owned()));
+/// ```
+///
+/// [`StyledString::with_link`]: struct.StyledString.html#method.with_link
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Link {
+    /// A URI that is opened by the PDF viewer, for example the address of a web page.
+    Uri(String),
+    /// A named destination within the document.
+    ///
+    /// Destinations can be added with the [`Anchor`][] element or with the
+    /// [`Area::add_destination`][] method.
+    ///
+    /// [`Anchor`]: ../elements/struct.Anchor.html
+    /// [`Area::add_destination`]: ../render/struct.Area.html#method.add_destination
+    Destination(String),
+}
+
+/// A footnote that is referenced by a styled string.
+///
+/// Footnotes can be attached to styled strings, see [`StyledString::with_footnote`][], or added to
+/// a paragraph, see [`Paragraph::push_footnote`][].  The styled string is printed as the footnote
+/// marker.  If it is empty, the footnote is numbered automatically.  The footnote text is printed
+/// at the bottom of the page that contains the marker, using the style of the marker’s paragraph
+/// combined with the style of the footnote.  If it does not fit on the page, it is continued on
+/// the next page.
+///
+/// # Examples
+///
+/// ```
+/// use genpdf::{elements, style};
+/// let footnote = style::Footnote::new("See the documentation.")
+///     .with_style(style::Style::new().with_font_size(8));
+/// let p = elements::Paragraph::new("This is important.").footnote(footnote);
+/// ```
+///
+/// [`StyledString::with_footnote`]: struct.StyledString.html#method.with_footnote
+/// [`Paragraph::push_footnote`]: ../elements/struct.Paragraph.html#method.push_footnote
+#[derive(Clone, Debug, Default)]
+pub struct Footnote {
+    text: Vec<StyledString>,
+    style: Style,
+}
+
+impl Footnote {
+    /// Creates a new footnote with the given text.
+    pub fn new(text: impl Into<StyledString>) -> Footnote {
+        Footnote {
+            text: vec![text.into()],
+            style: Style::new(),
+        }
+    }
+
+    /// Returns the text of this footnote.
+    pub fn text(&self) -> &[StyledString] {
+        &self.text
+    }
+
+    /// Returns the style of this footnote.
+    pub fn style(&self) -> Style {
+        self.style
+    }
+
+    /// Adds a string to the end of the text of this footnote.
+    pub fn push(&mut self, s: impl Into<StyledString>) {
+        self.text.push(s.into());
+    }
+
+    /// Adds a string to the end of the text of this footnote and returns the footnote.
+    pub fn string(mut self, s: impl Into<StyledString>) -> Footnote {
+        self.push(s);
+        self
+    }
+
+    /// Sets the style of this footnote.
+    pub fn set_style(&mut self, style: impl Into<Style>) {
+        self.style = style.into();
+    }
+
+    /// Sets the style of this footnote and returns the footnote.
+    pub fn with_style(mut self, style: impl Into<Style>) -> Footnote {
+        self.set_style(style);
+        self
+    }
+}
+
+impl<T: Into<StyledString>> From<T> for Footnote {
+    fn from(text: T) -> Footnote {
+        Footnote::new(text)
+    }
+}
+
 /// A [`String`][] with a [`Style`][] annotation.
 ///
 /// # Example
@@ -354,6 +1392,10 @@ pub struct StyledString {
     pub s: String,
     /// The style annotation.
     pub style: Style,
+    /// The link target of this string.
+    pub link: Option<Link>,
+    /// The footnote referenced by this string.
+    pub footnote: Option<Footnote>,
 }
 
 impl StyledString {
@@ -362,9 +1404,38 @@ impl StyledString {
         StyledString {
             s: s.into(),
             style: style.into(),
+            link: None,
+            footnote: None,
         }
     }
 
+    /// Sets the link target of this string.
+    pub fn set_link(&mut self, link: Link) {
+        self.link = Some(link);
+    }
+
+    /// Sets the link target of this string and returns the string.
+    pub fn with_link(mut self, link: Link) -> StyledString {
+        self.set_link(link);
+        self
+    }
+
+    /// Sets the footnote referenced by this string.
+    ///
+    /// This string is used as the footnote marker.  If it is empty, the footnote is numbered
+    /// automatically.  See [`Footnote`][] for more information.
+    ///
+    /// [`Footnote`]: struct.Footnote.html
+    pub fn set_footnote(&mut self, footnote: impl Into<Footnote>) {
+        self.footnote = Some(footnote.into());
+    }
+
+    /// Sets the footnote referenced by this string and returns the string.
+    pub fn with_footnote(mut self, footnote: impl Into<Footnote>) -> StyledString {
+        self.set_footnote(footnote);
+        self
+    }
+
     /// Calculates the width of the this string with this style using the data in the given font
     /// cache.
     ///
@@ -413,6 +1484,10 @@ pub struct StyledStr<'s> {
     pub s: &'s str,
     /// The style annotation.
     pub style: Style,
+    /// The link target of this string.
+    pub link: Option<&'s Link>,
+    /// The footnote referenced by this string.
+    pub footnote: Option<&'s Footnote>,
 }
 
 impl<'s> StyledStr<'s> {
@@ -421,6 +1496,8 @@ impl<'s> StyledStr<'s> {
         StyledStr {
             s,
             style: style.into(),
+            link: None,
+            footnote: None,
         }
     }
 
@@ -432,7 +1509,7 @@ impl<'s> StyledStr<'s> {
     ///
     /// [`FontCache`]: ../fonts/struct.FontCache.html
     pub fn width(&self, font_cache: &fonts::FontCache) -> Mm {
-        self.style.str_width(font_cache, &self.s)
+        self.style.str_width(font_cache, self.s)
     }
 }
 
@@ -450,7 +1527,12 @@ impl<'s> From<&'s String> for StyledStr<'s> {
 
 impl<'s> From<&'s StyledString> for StyledStr<'s> {
     fn from(s: &'s StyledString) -> StyledStr<'s> {
-        StyledStr::new(&s.s, s.style)
+        StyledStr {
+            s: &s.s,
+            style: s.style,
+            link: s.link.as_ref(),
+            footnote: s.footnote.as_ref(),
+        }
     }
 }
 
@@ -472,6 +1554,10 @@ pub struct StyledCow<'s> {
     pub s: borrow::Cow<'s, str>,
     /// The style annotation.
     pub style: Style,
+    /// The link target of this string.
+    pub link: Option<borrow::Cow<'s, Link>>,
+    /// The footnote referenced by this string.
+    pub footnote: Option<borrow::Cow<'s, Footnote>>,
 }
 
 impl<'s> StyledCow<'s> {
@@ -480,6 +1566,8 @@ impl<'s> StyledCow<'s> {
         StyledCow {
             s: s.into(),
             style: style.into(),
+            link: None,
+            footnote: None,
         }
     }
 
@@ -515,18 +1603,33 @@ impl<'s> From<String> for StyledCow<'s> {
 
 impl<'s> From<StyledStr<'s>> for StyledCow<'s> {
     fn from(s: StyledStr<'s>) -> StyledCow<'s> {
-        StyledCow::new(s.s, s.style)
+        StyledCow {
+            s: s.s.into(),
+            style: s.style,
+            link: s.link.map(borrow::Cow::Borrowed),
+            footnote: s.footnote.map(borrow::Cow::Borrowed),
+        }
     }
 }
 
 impl<'s> From<&'s StyledString> for StyledCow<'s> {
     fn from(s: &'s StyledString) -> StyledCow<'s> {
-        StyledCow::new(&s.s, s.style)
+        StyledCow {
+            s: s.s.as_str().into(),
+            style: s.style,
+            link: s.link.as_ref().map(borrow::Cow::Borrowed),
+            footnote: s.footnote.as_ref().map(borrow::Cow::Borrowed),
+        }
     }
 }
 
 impl<'s> From<StyledString> for StyledCow<'s> {
     fn from(s: StyledString) -> StyledCow<'s> {
-        StyledCow::new(s.s, s.style)
+        StyledCow {
+            s: s.s.into(),
+            style: s.style,
+            link: s.link.map(borrow::Cow::Owned),
+            footnote: s.footnote.map(borrow::Cow::Owned),
+        }
     }
 }
@@ -9,6 +9,12 @@ use crate::style;
 use crate::Context;
 use crate::Mm;
 
+/// The tolerance for the comparison of line widths.
+///
+/// Widths calculated by containers, for example automatic column widths, are subject to rounding
+/// errors.  Therefore we accept lines that exceed the maximum width by a negligible amount.
+const TOLERANCE: Mm = Mm(1e-6);
+
 /// Combines a sequence of styled words into lines with a maximum width.
 ///
 /// If a word does not fit into a line, the wrapper tries to split it using the `split` function.
@@ -40,10 +46,10 @@ impl<'c, 's, I: Iterator<Item = style::StyledStr<'s>>> Iterator for Wrapper<'c,
 
     fn next(&mut self) -> Option<(Vec<style::StyledCow<'s>>, usize)> {
         // Append words to self.buf until the maximum line length is reached
-        while let Some(s) = self.iter.next() {
+        for s in self.iter.by_ref() {
             let mut width = s.width(&self.context.font_cache);
 
-            if self.x + width > self.width {
+            if self.x + width > self.width + TOLERANCE {
                 // The word does not fit into the current line (at least not completely)
 
                 let mut delta = 0;
@@ -59,7 +65,7 @@ impl<'c, 's, I: Iterator<Item = style::StyledStr<'s>>> Iterator for Wrapper<'c,
                     s.into()
                 };
 
-                if width > self.width {
+                if width > self.width + TOLERANCE {
                     // The remainder of the word is longer than the current page – we will never be
                     // able to render it completely.
                     // TODO: return error?
@@ -104,6 +110,7 @@ fn split<'s>(
     width: Mm,
 ) -> Option<(style::StyledCow<'s>, style::StyledCow<'s>)> {
     use hyphenation::{Hyphenator, Iter};
+    use std::borrow;
 
     let hyphenator = if let Some(hyphenator) = &context.hyphenator {
         hyphenator
@@ -131,10 +138,13 @@ fn split<'s>(
         let idx = hyphenated.breaks[idx - 1];
         let start = s.s[..idx].to_owned() + mark;
         let end = &s.s[idx..];
-        Some((
-            style::StyledCow::new(start, s.style),
-            style::StyledCow::new(end, s.style),
-        ))
+        let mut start = style::StyledCow::new(start, s.style);
+        let mut end = style::StyledCow::new(end, s.style);
+        start.link = s.link.map(borrow::Cow::Borrowed);
+        end.link = start.link.clone();
+        // The footnote marker is printed after the word, so we only keep it for the second part.
+        end.footnote = s.footnote.map(borrow::Cow::Borrowed);
+        Some((start, end))
     } else {
         None
     }
@@ -171,7 +181,14 @@ impl<I: Iterator<Item = style::StyledString>> Iterator for Words<I> {
             let n = s.s.find(' ').map(|i| i + 1).unwrap_or_else(|| s.s.len());
             let mut tmp = s.s.split_off(n);
             mem::swap(&mut tmp, &mut s.s);
-            Some(style::StyledString::new(tmp, s.style))
+            let mut word = style::StyledString::new(tmp, s.style);
+            word.link = s.link.clone();
+            if s.s.is_empty() {
+                // The footnote marker is printed after the string, so we attach the footnote to
+                // the last word.
+                word.footnote = s.footnote.take();
+            }
+            Some(word)
         } else {
             None
         }